@@ -0,0 +1,81 @@
+//! Freezing of the [Stream] tokens of non-transferable [Stream]s.
+
+use crate::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, FreezeAccount, Mint, ThawAccount, Token, TokenAccount};
+
+/// Accounts used to freeze and thaw a holder's [Stream] tokens.
+///
+/// The [Stream] is the freeze authority of the mint of a non-transferable
+/// [Stream], and keeps the tokens of its [Stream::beneficiary] frozen so
+/// that they may not be moved to another owner. The tokens are only thawed
+/// for the duration of an instruction which burns or issues them.
+pub(crate) struct StreamTokensFreeze<'a, 'info> {
+    /// The [Stream].
+    pub stream: &'a Account<'info, VersionedStream>,
+    /// [Mint] of the [Stream].
+    pub stream_mint: &'a Account<'info, Mint>,
+    /// The [TokenAccount] holding the [Stream] tokens.
+    pub stream_tokens: &'a Account<'info, TokenAccount>,
+    /// SPL [anchor_spl::token] program.
+    pub token_program: &'a Program<'info, Token>,
+}
+
+impl<'a, 'info> StreamTokensFreeze<'a, 'info> {
+    /// Returns true if the [Stream] is the freeze authority of its mint.
+    pub(crate) fn is_freeze_authority(&self) -> bool {
+        self.stream_mint.freeze_authority == COption::Some(self.stream.key())
+    }
+
+    /// Runs `f` with the [Self::stream_tokens] thawed, then freezes them
+    /// again if the [Stream] is the freeze authority of its mint.
+    ///
+    /// `remaining_amount` is the balance of the [Self::stream_tokens] after
+    /// `f`. An empty account is left thawed so that its owner may close it.
+    pub(crate) fn with_thawed<F>(&self, remaining_amount: u64, f: F) -> ProgramResult
+    where
+        F: FnOnce() -> ProgramResult,
+    {
+        self.thaw()?;
+        f()?;
+        if remaining_amount > 0 {
+            self.freeze()?;
+        }
+        Ok(())
+    }
+
+    /// Thaws the [Self::stream_tokens] if the [Stream] has frozen them.
+    pub(crate) fn thaw(&self) -> ProgramResult {
+        if !self.is_freeze_authority() || !self.stream_tokens.is_frozen() {
+            return Ok(());
+        }
+        let signer_seeds: &[&[&[u8]]] = stream_seeds!(self.stream);
+        token::thaw_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            ThawAccount {
+                account: self.stream_tokens.to_account_info(),
+                mint: self.stream_mint.to_account_info(),
+                authority: self.stream.to_account_info(),
+            },
+            signer_seeds,
+        ))
+    }
+
+    /// Freezes the [Self::stream_tokens] if the [Stream] is the freeze
+    /// authority of its mint. They must not already be frozen.
+    pub(crate) fn freeze(&self) -> ProgramResult {
+        if !self.is_freeze_authority() {
+            return Ok(());
+        }
+        let signer_seeds: &[&[&[u8]]] = stream_seeds!(self.stream);
+        token::freeze_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            FreezeAccount {
+                account: self.stream_tokens.to_account_info(),
+                mint: self.stream_mint.to_account_info(),
+                authority: self.stream.to_account_info(),
+            },
+            signer_seeds,
+        ))
+    }
+}
@@ -0,0 +1,57 @@
+//! Instruction handler for [crate::venko::accept_revoker].
+
+use crate::*;
+use vipers::{assert_keys_eq, invariant, Validate};
+
+/// Accounts for [venko::accept_revoker].
+#[derive(Accounts)]
+pub struct AcceptRevoker<'info> {
+    /// [Stream] account.
+    #[account(mut)]
+    pub stream: Box<Account<'info, VersionedStream>>,
+    /// The [Stream::pending_revoker].
+    pub pending_revoker: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptRevoker>) -> ProgramResult {
+    let stream = &mut ctx.accounts.stream;
+    let prev_revoker = stream.revoker;
+    stream.revoker = stream.pending_revoker;
+    stream.pending_revoker = Pubkey::default();
+
+    emit!(RevokerAcceptEvent {
+        stream: stream.key(),
+        prev_revoker,
+        revoker: stream.revoker,
+    });
+
+    Ok(())
+}
+
+/// Emitted on [crate::venko::accept_revoker].
+#[event]
+pub struct RevokerAcceptEvent {
+    /// The [Stream].
+    #[index]
+    pub stream: Pubkey,
+    /// The previous [Stream::revoker].
+    pub prev_revoker: Pubkey,
+    /// The new [Stream::revoker].
+    pub revoker: Pubkey,
+}
+
+impl<'info> Validate<'info> for AcceptRevoker<'info> {
+    fn validate(&self) -> ProgramResult {
+        invariant!(
+            self.stream.pending_revoker != Pubkey::default(),
+            NotPendingRevoker
+        );
+        assert_keys_eq!(
+            self.stream.pending_revoker,
+            self.pending_revoker,
+            NotPendingRevoker
+        );
+        self.stream.assert_not_finished()?;
+        Ok(())
+    }
+}
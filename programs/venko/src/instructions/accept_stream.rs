@@ -0,0 +1,70 @@
+//! Instruction handler for [crate::venko::accept_stream].
+
+use crate::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use vipers::{assert_keys_eq, invariant, unwrap_int, Validate};
+
+/// Accounts for [venko::accept_stream].
+#[derive(Accounts)]
+pub struct AcceptStream<'info> {
+    /// [Mint] of the [Stream].
+    pub stream_mint: Account<'info, Mint>,
+    /// [Stream] account.
+    #[account(mut)]
+    pub stream: Box<Account<'info, VersionedStream>>,
+
+    /// The [TokenAccount] holding the entire supply of [Stream] tokens.
+    pub recipient_stream_tokens: Account<'info, TokenAccount>,
+    /// Owner of the [Self::recipient_stream_tokens].
+    pub recipient: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptStream>) -> ProgramResult {
+    let stream = &mut ctx.accounts.stream;
+    unwrap_int!(stream.accept(Clock::get()?.unix_timestamp));
+
+    emit!(StreamAcceptEvent {
+        stream: stream.key(),
+        recipient: ctx.accounts.recipient.key(),
+        start_ts: stream.start_ts,
+        end_ts: stream.end_ts,
+    });
+
+    Ok(())
+}
+
+/// Emitted on [crate::venko::accept_stream].
+#[event]
+pub struct StreamAcceptEvent {
+    /// The [Stream].
+    #[index]
+    pub stream: Pubkey,
+    /// The recipient who accepted the [Stream].
+    pub recipient: Pubkey,
+    /// The new [Stream::start_ts].
+    pub start_ts: i64,
+    /// The new [Stream::end_ts].
+    pub end_ts: i64,
+}
+
+impl<'info> Validate<'info> for AcceptStream<'info> {
+    fn validate(&self) -> ProgramResult {
+        assert_keys_eq!(self.stream_mint, self.stream.mint);
+        invariant!(
+            self.stream.status == StreamStatus::Pending,
+            StreamNotPending
+        );
+        invariant!(
+            Clock::get()?.unix_timestamp <= self.stream.acceptance_deadline,
+            AcceptanceDeadlinePassed
+        );
+
+        assert_keys_eq!(self.recipient_stream_tokens.mint, self.stream.mint);
+        assert_keys_eq!(self.recipient_stream_tokens.owner, self.recipient);
+        invariant!(
+            self.recipient_stream_tokens.amount == self.stream_mint.supply,
+            NotSoleHolder
+        );
+        Ok(())
+    }
+}
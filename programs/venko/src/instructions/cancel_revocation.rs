@@ -0,0 +1,40 @@
+//! Instruction handler for [crate::venko::cancel_revocation].
+
+use crate::*;
+use vipers::invariant;
+
+pub fn handler(ctx: Context<ScheduleRevocation>) -> ProgramResult {
+    let now = Clock::get()?.unix_timestamp;
+    invariant!(
+        ctx.accounts.stream.scheduled_revoke_ts != 0,
+        RevocationNotScheduled
+    );
+    invariant!(
+        !ctx.accounts.scheduled_revocation_passed(now),
+        RevocationPassed
+    );
+
+    let stream = &mut ctx.accounts.stream;
+    let revoke_ts = stream.scheduled_revoke_ts;
+    stream.scheduled_revoke_ts = 0;
+
+    emit!(RevocationCancelEvent {
+        stream: stream.key(),
+        revoker: ctx.accounts.revoker.key(),
+        revoke_ts,
+    });
+
+    Ok(())
+}
+
+/// Emitted on [crate::venko::cancel_revocation].
+#[event]
+pub struct RevocationCancelEvent {
+    /// The [Stream].
+    #[index]
+    pub stream: Pubkey,
+    /// The [Stream::revoker].
+    pub revoker: Pubkey,
+    /// The time at which the [Stream] would have been revoked.
+    pub revoke_ts: i64,
+}
@@ -0,0 +1,98 @@
+//! Instruction handler for [crate::venko::cancel_stream].
+
+use crate::*;
+use anchor_spl::token::{self, TokenAccount};
+use vipers::{assert_keys_eq, invariant, Validate};
+
+/// Accounts for [venko::cancel_stream].
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    /// [Stream] account.
+    #[account(mut)]
+    pub stream: Box<Account<'info, VersionedStream>>,
+
+    /// Crate token. Its withdraw fee is lifted for the refund.
+    #[account(mut)]
+    pub crate_token: Box<Account<'info, crate_token::CrateToken>>,
+
+    /// Underlying tokens of the [Stream].
+    #[account(mut)]
+    pub underlying_tokens: Account<'info, TokenAccount>,
+    /// Destination of the refunded underlying tokens.
+    #[account(mut)]
+    pub destination_tokens: Account<'info, TokenAccount>,
+
+    /// The [Stream::creator].
+    pub creator: Signer<'info>,
+
+    /// [crate_token] program.
+    pub crate_token_program: Program<'info, crate_token::program::CrateToken>,
+    /// SPL [token] program.
+    pub token_program: Program<'info, token::Token>,
+}
+
+impl<'info> CancelStream<'info> {
+    /// Withdraws all underlying tokens to the [Self::destination_tokens].
+    fn refund(&self) -> Result<u64> {
+        let amount = self.underlying_tokens.amount;
+        StreamWithdraw::refund(
+            &self.stream,
+            &self.crate_token,
+            &self.underlying_tokens,
+            &self.destination_tokens,
+            &self.crate_token_program,
+            &self.token_program,
+            amount,
+        )?;
+        Ok(amount)
+    }
+}
+
+pub fn handler(ctx: Context<CancelStream>) -> ProgramResult {
+    let amount = ctx.accounts.refund()?;
+
+    let stream = &mut ctx.accounts.stream;
+    stream.status = StreamStatus::Cancelled;
+
+    emit!(StreamCancelEvent {
+        stream: stream.key(),
+        mint: stream.underlying_mint,
+        creator: ctx.accounts.creator.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+/// Emitted on [crate::venko::cancel_stream].
+#[event]
+pub struct StreamCancelEvent {
+    /// The [Stream].
+    #[index]
+    pub stream: Pubkey,
+    /// Mint of the underlying token.
+    #[index]
+    pub mint: Pubkey,
+    /// The [Stream::creator].
+    pub creator: Pubkey,
+    /// Amount of underlying tokens refunded.
+    pub amount: u64,
+}
+
+impl<'info> Validate<'info> for CancelStream<'info> {
+    fn validate(&self) -> ProgramResult {
+        assert_keys_eq!(self.stream.creator, self.creator, NotCreator);
+        let now = Clock::get()?.unix_timestamp;
+        if self.stream.status == StreamStatus::Pending {
+            invariant!(now > self.stream.acceptance_deadline, AcceptancePending);
+        } else {
+            self.stream.assert_active()?;
+            invariant!(self.stream.is_cancellable(now), CancellationWindowClosed);
+        }
+
+        assert_keys_eq!(self.crate_token, self.stream.crate_token);
+        assert_keys_eq!(self.underlying_tokens, self.stream.underlying_tokens);
+        assert_keys_eq!(self.destination_tokens.mint, self.stream.underlying_mint);
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+//! Instruction handler for [crate::venko::claim_vested].
+
+use crate::*;
+use anchor_spl::token::{self, TokenAccount, Transfer};
+use vipers::{assert_keys_eq, invariant, unwrap_int, Validate};
+
+/// Accounts for [venko::claim_vested].
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    /// [VestingDistributor] account.
+    #[account(mut)]
+    pub distributor: Account<'info, VestingDistributor>,
+    /// The [ClaimStatus] of the [Self::claimant].
+    #[account(mut)]
+    pub claim_status: Account<'info, ClaimStatus>,
+
+    /// Underlying tokens of the [VestingDistributor].
+    #[account(mut)]
+    pub underlying_tokens: Account<'info, TokenAccount>,
+    /// Destination of the claimed tokens.
+    #[account(mut)]
+    pub destination_tokens: Account<'info, TokenAccount>,
+
+    /// The claimant.
+    pub claimant: Signer<'info>,
+
+    /// SPL [token] program.
+    pub token_program: Program<'info, token::Token>,
+}
+
+impl<'info> ClaimVested<'info> {
+    /// Computes the amount of the [ClaimStatus::allocation] released but not
+    /// yet redeemed.
+    fn amount_released(&self) -> Result<u64> {
+        let total_released = unwrap_int!(self
+            .distributor
+            .total_released(self.claim_status.allocation, Clock::get()?.unix_timestamp,));
+        Ok(unwrap_int!(
+            total_released.checked_sub(self.claim_status.redeemed_amount)
+        ))
+    }
+
+    fn transfer_underlying(&self, amount: u64) -> ProgramResult {
+        let signer_seeds: &[&[&[u8]]] = distributor_seeds!(self.distributor);
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.underlying_tokens.to_account_info(),
+                    to: self.destination_tokens.to_account_info(),
+                    authority: self.distributor.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<ClaimVested>) -> ProgramResult {
+    let amount = ctx.accounts.amount_released()?;
+    invariant!(amount > 0, InsufficientWithdrawalBalance);
+    ctx.accounts.transfer_underlying(amount)?;
+
+    let claim_status = &mut ctx.accounts.claim_status;
+    claim_status.redeemed_amount = unwrap_int!(claim_status.redeemed_amount.checked_add(amount));
+
+    let distributor = &mut ctx.accounts.distributor;
+    distributor.total_redeemed = unwrap_int!(distributor.total_redeemed.checked_add(amount));
+    invariant!(
+        distributor.total_redeemed <= distributor.max_total_claim,
+        ExceededMaxClaim
+    );
+
+    emit!(VestedClaimEvent {
+        distributor: distributor.key(),
+        claimant: claim_status.claimant,
+        amount,
+        redeemed_amount: claim_status.redeemed_amount,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct VestedClaimEvent {
+    #[index]
+    pub distributor: Pubkey,
+    #[index]
+    pub claimant: Pubkey,
+    pub amount: u64,
+    pub redeemed_amount: u64,
+}
+
+impl<'info> Validate<'info> for ClaimVested<'info> {
+    fn validate(&self) -> ProgramResult {
+        assert_keys_eq!(self.claim_status.distributor, self.distributor);
+        assert_keys_eq!(self.claim_status.claimant, self.claimant);
+        assert_keys_eq!(self.underlying_tokens, self.distributor.underlying_tokens);
+        assert_keys_eq!(
+            self.destination_tokens.mint,
+            self.distributor.underlying_mint
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+//! Instruction handler for [crate::venko::close_stream].
+
+use crate::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use vipers::{assert_keys_eq, invariant, Validate};
+
+/// Accounts for [venko::close_stream].
+#[derive(Accounts)]
+pub struct CloseStream<'info> {
+    /// [Stream] account.
+    #[account(mut, close = recipient)]
+    pub stream: Box<Account<'info, VersionedStream>>,
+    /// [Mint] of the [Stream].
+    pub stream_mint: Account<'info, Mint>,
+    /// Underlying tokens of the [Stream].
+    pub underlying_tokens: Account<'info, TokenAccount>,
+
+    /// The [Stream::creator].
+    pub creator: Signer<'info>,
+    /// Recipient of the rent of the [Stream] account.
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CloseStream>) -> ProgramResult {
+    let stream = &ctx.accounts.stream;
+    emit!(StreamCloseEvent {
+        stream: stream.key(),
+        mint: stream.mint,
+        recipient: ctx.accounts.recipient.key(),
+    });
+
+    Ok(())
+}
+
+/// Emitted on [crate::venko::close_stream].
+#[event]
+pub struct StreamCloseEvent {
+    /// The [Stream].
+    #[index]
+    pub stream: Pubkey,
+    /// Mint of the [Stream] token.
+    #[index]
+    pub mint: Pubkey,
+    /// Recipient of the rent.
+    pub recipient: Pubkey,
+}
+
+impl<'info> Validate<'info> for CloseStream<'info> {
+    fn validate(&self) -> ProgramResult {
+        assert_keys_eq!(self.stream.creator, self.creator, NotCreator);
+        self.stream.assert_finished()?;
+
+        assert_keys_eq!(self.stream_mint, self.stream.mint);
+        invariant!(self.stream_mint.supply == 0, StreamNotDrained);
+
+        assert_keys_eq!(self.underlying_tokens, self.stream.underlying_tokens);
+        invariant!(self.underlying_tokens.amount == 0, StreamNotDrained);
+        Ok(())
+    }
+}
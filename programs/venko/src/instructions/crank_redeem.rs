@@ -0,0 +1,179 @@
+//! Instruction handler for [crate::venko::crank_redeem].
+
+use crate::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, Burn, Mint, TokenAccount};
+use vipers::{assert_keys_eq, invariant, unwrap_int, Validate};
+
+/// Accounts for [venko::crank_redeem].
+#[derive(Accounts)]
+pub struct CrankRedeem<'info> {
+    /// [token::Mint] of the [Stream].
+    /// This account is `mut` because tokens are burned.
+    #[account(mut)]
+    pub stream_mint: Account<'info, Mint>,
+    /// [Stream] account.
+    #[account(mut)]
+    pub stream: Box<Account<'info, VersionedStream>>,
+
+    /// The [TokenAccount] holding the holder's stream tokens. The [Stream]
+    /// must be its approved delegate.
+    #[account(mut)]
+    pub source_stream_tokens: Account<'info, TokenAccount>,
+    /// The [HolderClaim] of the owner of the [Self::source_stream_tokens].
+    #[account(mut)]
+    pub holder_claim: Box<Account<'info, HolderClaim>>,
+
+    /// Underlying tokens of the [Stream].
+    #[account(mut)]
+    pub underlying_tokens: Account<'info, TokenAccount>,
+    /// The holder's associated token account of the underlying mint.
+    #[account(mut)]
+    pub destination_tokens: Account<'info, TokenAccount>,
+    /// Destination of the author fees charged by the Crate. Must be owned by
+    /// the [Stream::author_fee_to] if the [Stream] charges fees.
+    #[account(mut)]
+    pub author_fee_destination: Account<'info, TokenAccount>,
+    /// Destination of the protocol fees charged by the Crate.
+    #[account(mut)]
+    pub protocol_fee_destination: Account<'info, TokenAccount>,
+
+    /// The [crate_token::CrateToken].
+    pub crate_token: Box<Account<'info, crate_token::CrateToken>>,
+
+    /// [crate_token] program.
+    pub crate_token_program: Program<'info, crate_token::program::CrateToken>,
+    /// SPL [token] program.
+    pub token_program: Program<'info, token::Token>,
+}
+
+impl<'info> CrankRedeem<'info> {
+    /// Computes the amount of underlying tokens to redeem for the holder.
+    fn amount_released(&self) -> Result<u64> {
+        let amount_released = unwrap_int!(self.stream.holder_available_for_withdrawal(
+            Clock::get()?.unix_timestamp,
+            self.underlying_tokens.amount,
+            self.source_stream_tokens.amount,
+            self.holder_claim.redeemed_amount,
+        ));
+        Ok(amount_released.min(self.source_stream_tokens.delegated_amount))
+    }
+
+    /// Burns `amount` [Stream] tokens, thawing the [Self::source_stream_tokens]
+    /// for the burn if they are frozen.
+    fn burn_stream_tokens(&self, amount: u64) -> ProgramResult {
+        let remaining_amount = unwrap_int!(self.source_stream_tokens.amount.checked_sub(amount));
+        StreamTokensFreeze {
+            stream: &self.stream,
+            stream_mint: &self.stream_mint,
+            stream_tokens: &self.source_stream_tokens,
+            token_program: &self.token_program,
+        }
+        .with_thawed(remaining_amount, || self.burn(amount))
+    }
+
+    fn burn(&self, amount: u64) -> ProgramResult {
+        let signer_seeds: &[&[&[u8]]] = stream_seeds!(self.stream);
+        token::burn(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.stream_mint.to_account_info(),
+                    to: self.source_stream_tokens.to_account_info(),
+                    authority: self.stream.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )
+    }
+
+    fn withdraw_crate_tokens(&self, amount: u64) -> Result<crate_token::state::Fees> {
+        StreamWithdraw {
+            stream: &self.stream,
+            crate_token: &self.crate_token,
+            underlying_tokens: &self.underlying_tokens,
+            destination_tokens: &self.destination_tokens,
+            author_fee_destination: &self.author_fee_destination,
+            protocol_fee_destination: &self.protocol_fee_destination,
+            crate_token_program: &self.crate_token_program,
+            token_program: &self.token_program,
+        }
+        .withdraw(amount)
+    }
+}
+
+pub fn handler(ctx: Context<CrankRedeem>) -> ProgramResult {
+    let amount = ctx.accounts.amount_released()?;
+    invariant!(amount > 0, InsufficientWithdrawalBalance);
+
+    // redeem the crate tokens
+    ctx.accounts.burn_stream_tokens(amount)?;
+    let fees = ctx.accounts.withdraw_crate_tokens(amount)?;
+
+    let holder_claim = &mut ctx.accounts.holder_claim;
+    holder_claim.redeemed_amount = unwrap_int!(holder_claim.redeemed_amount.checked_add(amount));
+
+    let stream = &mut ctx.accounts.stream;
+    unwrap_int!(stream.record_redemption(amount));
+
+    let amount_remaining = unwrap_int!(stream.initial_amount.checked_sub(stream.redeemed_amount));
+
+    emit!(RedeemEvent {
+        stream: stream.key(),
+        mint: stream.underlying_mint,
+        amount,
+        amount_remaining,
+        author_fee: fees.author_fee,
+        protocol_fee: fees.protocol_fee,
+    });
+
+    Ok(())
+}
+
+impl<'info> Validate<'info> for CrankRedeem<'info> {
+    fn validate(&self) -> ProgramResult {
+        invariant!(self.stream.auto_redeem, AutoRedeemDisabled);
+        self.stream.assert_redeemable()?;
+        invariant!(
+            !self.stream.is_cancellable(Clock::get()?.unix_timestamp),
+            CancellationWindowOpen
+        );
+        assert_keys_eq!(self.stream_mint, self.stream.mint);
+
+        assert_keys_eq!(self.source_stream_tokens.mint, self.stream.mint);
+        if !self.stream.is_transferable() {
+            assert_keys_eq!(
+                self.source_stream_tokens.owner,
+                self.stream.beneficiary,
+                NotBeneficiary
+            );
+        }
+        invariant!(
+            self.source_stream_tokens.delegate == COption::Some(self.stream.key()),
+            StreamNotDelegate
+        );
+        invariant!(
+            self.source_stream_tokens.amount > 0,
+            InsufficientStreamTokens
+        );
+        assert_keys_eq!(self.holder_claim.stream, self.stream);
+        assert_keys_eq!(self.holder_claim.owner, self.source_stream_tokens.owner);
+
+        assert_keys_eq!(self.underlying_tokens, self.stream.underlying_tokens);
+        // tokens may only be sent to the holder
+        assert_keys_eq!(
+            self.destination_tokens,
+            get_associated_token_address(
+                &self.source_stream_tokens.owner,
+                &self.stream.underlying_mint
+            )
+        );
+
+        assert_keys_eq!(self.crate_token, self.stream.crate_token);
+        self.stream
+            .assert_author_fee_owner(self.author_fee_destination.owner)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+//! Instruction handler for [crate::venko::create_claim_status].
+
+use crate::*;
+use vipers::{invariant, Validate};
+
+/// Accounts for [venko::create_claim_status].
+#[derive(Accounts)]
+#[instruction(bump: u8)]
+pub struct CreateClaimStatus<'info> {
+    /// [VestingDistributor] account.
+    pub distributor: Account<'info, VestingDistributor>,
+    /// The claimant whose allocation is proven.
+    pub claimant: UncheckedAccount<'info>,
+    /// The [ClaimStatus] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"ClaimStatus",
+            distributor.key().as_ref(),
+            claimant.key().as_ref()
+        ],
+        bump = bump,
+        payer = payer
+    )]
+    pub claim_status: Account<'info, ClaimStatus>,
+
+    /// Payer for the [ClaimStatus] account creation.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateClaimStatus>,
+    bump: u8,
+    allocation: u64,
+    proof: Vec<[u8; 32]>,
+) -> ProgramResult {
+    let distributor = &ctx.accounts.distributor;
+    let leaf = merkle_proof::leaf(&ctx.accounts.claimant.key(), allocation);
+    invariant!(
+        merkle_proof::verify(&proof, distributor.root, leaf),
+        InvalidProof
+    );
+    invariant!(allocation <= distributor.max_total_claim, ExceededMaxClaim);
+
+    let claim_status = &mut ctx.accounts.claim_status;
+    claim_status.distributor = distributor.key();
+    claim_status.claimant = ctx.accounts.claimant.key();
+    claim_status.bump = bump;
+    claim_status.allocation = allocation;
+    claim_status.redeemed_amount = 0;
+    Ok(())
+}
+
+impl<'info> Validate<'info> for CreateClaimStatus<'info> {
+    fn validate(&self) -> ProgramResult {
+        // nothing is left to claim once every allocation has been redeemed
+        invariant!(
+            self.distributor.total_redeemed < self.distributor.max_total_claim,
+            DistributorExhausted
+        );
+        Ok(())
+    }
+}
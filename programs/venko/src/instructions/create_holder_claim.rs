@@ -0,0 +1,40 @@
+//! Instruction handler for [crate::venko::create_holder_claim].
+
+use crate::*;
+
+/// Accounts for [venko::create_holder_claim].
+#[derive(Accounts)]
+#[instruction(bump: u8)]
+pub struct CreateHolderClaim<'info> {
+    /// [Stream] account.
+    pub stream: Box<Account<'info, VersionedStream>>,
+    /// The owner of the [Stream] tokens to track.
+    pub owner: UncheckedAccount<'info>,
+    /// The [HolderClaim] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"HolderClaim",
+            stream.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump = bump,
+        payer = payer
+    )]
+    pub holder_claim: Account<'info, HolderClaim>,
+
+    /// Payer for the [HolderClaim] account creation.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateHolderClaim>, bump: u8) -> ProgramResult {
+    let holder_claim = &mut ctx.accounts.holder_claim;
+    holder_claim.stream = ctx.accounts.stream.key();
+    holder_claim.owner = ctx.accounts.owner.key();
+    holder_claim.bump = bump;
+    holder_claim.redeemed_amount = 0;
+    Ok(())
+}
@@ -1,12 +1,21 @@
 //! Instruction handler for [crate::venko::create_stream].
 
 use crate::*;
-use anchor_spl::token::{self, Mint, TokenAccount};
-use vipers::{assert_keys_eq, invariant, Validate};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, TokenAccount, Transfer};
+use num_traits::ToPrimitive;
+use vipers::{assert_keys_eq, invariant, unwrap_int, Validate};
 
 /// Accounts for [venko::create_stream].
 #[derive(Accounts)]
-#[instruction(bump: u8)]
+#[instruction(
+    bump: u8,
+    crate_bump: u8,
+    creator_index_bump: u8,
+    creator_entry_bump: u8,
+    recipient_index_bump: u8,
+    recipient_entry_bump: u8
+)]
 pub struct CreateStream<'info> {
     /// [token::Mint] of the [Stream].
     #[account(mut)]
@@ -19,21 +28,89 @@ pub struct CreateStream<'info> {
             stream_mint.key().as_ref()
         ],
         bump = bump,
-        payer = payer
+        payer = payer,
+        space = Stream::LEN
     )]
-    pub stream: Account<'info, Stream>,
+    pub stream: Box<Account<'info, VersionedStream>>,
     /// Underlying mint.
     pub underlying_mint: Box<Account<'info, Mint>>,
     /// The [TokenAccount] holding the [Stream]'s tokens.
-    /// Must be owned by the [Self::crate_token], and the amount should be > 0.
+    /// This is the associated token account of the [Self::crate_token], and
+    /// is created if it does not exist.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = underlying_mint,
+        associated_token::authority = crate_token
+    )]
     pub underlying_tokens: Box<Account<'info, TokenAccount>>,
+    /// Source of the underlying tokens deposited into the [Stream].
+    #[account(mut)]
+    pub source_tokens: Box<Account<'info, TokenAccount>>,
+    /// Authority of the [Self::source_tokens].
+    pub source_authority: Signer<'info>,
     /// Destination of the [Stream] tokens.
     #[account(mut)]
-    pub destination: Account<'info, TokenAccount>,
+    pub destination: Box<Account<'info, TokenAccount>>,
 
     /// The [crate_token::CrateToken] to be created.
     #[account(mut)]
     pub crate_token: UncheckedAccount<'info>,
+    /// Recipient of the author fees charged by the Crate on withdrawals.
+    /// This should be the [System] program if the [Stream] charges no fees.
+    pub author_fee_to: UncheckedAccount<'info>,
+
+    /// The [StreamIndex] of the [Stream]s created by the
+    /// [Self::source_authority]. Created if it does not exist.
+    #[account(
+        init_if_needed,
+        seeds = [
+            b"CreatorIndex",
+            source_authority.key().as_ref()
+        ],
+        bump = creator_index_bump,
+        payer = payer
+    )]
+    pub creator_index: Box<Account<'info, StreamIndex>>,
+    /// The [StreamIndexEntry] pointing at the [Stream] in the
+    /// [Self::creator_index].
+    #[account(
+        init,
+        seeds = [
+            b"StreamIndexEntry",
+            creator_index.key().as_ref(),
+            creator_index.count.to_le_bytes().as_ref()
+        ],
+        bump = creator_entry_bump,
+        payer = payer
+    )]
+    pub creator_index_entry: Box<Account<'info, StreamIndexEntry>>,
+    /// The [StreamIndex] of the [Stream]s issued to the owner of the
+    /// [Self::destination]. Created if it does not exist.
+    #[account(
+        init_if_needed,
+        seeds = [
+            b"RecipientIndex",
+            destination.owner.as_ref()
+        ],
+        bump = recipient_index_bump,
+        payer = payer
+    )]
+    pub recipient_index: Box<Account<'info, StreamIndex>>,
+    /// The [StreamIndexEntry] pointing at the [Stream] in the
+    /// [Self::recipient_index].
+    #[account(
+        init,
+        seeds = [
+            b"StreamIndexEntry",
+            recipient_index.key().as_ref(),
+            recipient_index.count.to_le_bytes().as_ref()
+        ],
+        bump = recipient_entry_bump,
+        payer = payer
+    )]
+    pub recipient_index_entry: Box<Account<'info, StreamIndexEntry>>,
+
     /// Payer for the [Stream] account creation.
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -44,9 +121,30 @@ pub struct CreateStream<'info> {
     pub crate_token_program: Program<'info, crate_token::program::CrateToken>,
     /// SPL [token] program.
     pub token_program: Program<'info, token::Token>,
+    /// SPL [anchor_spl::associated_token] program.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// [Rent] sysvar.
+    pub rent: Sysvar<'info, Rent>,
 }
 
 impl<'info> CreateStream<'info> {
+    /// Transfers the underlying tokens from the [Self::source_tokens] into
+    /// the [Self::underlying_tokens].
+    fn deposit_underlying(&mut self, amount: u64) -> ProgramResult {
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.source_tokens.to_account_info(),
+                    to: self.underlying_tokens.to_account_info(),
+                    authority: self.source_authority.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        self.underlying_tokens.reload()
+    }
+
     fn init_crate(&self, crate_bump: u8) -> ProgramResult {
         crate_token::cpi::new_crate(
             CpiContext::new(
@@ -55,10 +153,11 @@ impl<'info> CreateStream<'info> {
                     crate_mint: self.stream_mint.to_account_info(),
                     crate_token: self.crate_token.to_account_info(),
 
-                    // no fees
+                    // the fee recipient may not be changed, and the fees may
+                    // only be set by the Stream on creation
                     fee_to_setter: self.system_program.to_account_info(),
-                    fee_setter_authority: self.system_program.to_account_info(),
-                    author_fee_to: self.system_program.to_account_info(),
+                    fee_setter_authority: self.stream.to_account_info(),
+                    author_fee_to: self.author_fee_to.to_account_info(),
 
                     // authorities
                     issue_authority: self.stream.to_account_info(),
@@ -71,6 +170,22 @@ impl<'info> CreateStream<'info> {
         )
     }
 
+    /// Sets the fee charged by the Crate on withdrawals.
+    fn set_withdraw_fee(&self, withdraw_fee_bps: u16) -> ProgramResult {
+        let signer_seeds: &[&[&[u8]]] = stream_seeds!(self.stream);
+        crate_token::cpi::set_withdraw_fee(
+            CpiContext::new_with_signer(
+                self.crate_token_program.to_account_info(),
+                crate_token::cpi::accounts::SetFees {
+                    crate_token: self.crate_token.to_account_info(),
+                    fee_setter: self.stream.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            withdraw_fee_bps,
+        )
+    }
+
     /// Issue the [Stream] tokens.
     fn issue_tokens(&self, amount: u64) -> ProgramResult {
         let signer_seeds: &[&[&[u8]]] = stream_seeds!(self.stream);
@@ -95,19 +210,87 @@ impl<'info> CreateStream<'info> {
         )
     }
 
+    /// Freezes the [Stream] tokens of a non-transferable [Stream].
+    fn freeze_tokens(&self) -> ProgramResult {
+        StreamTokensFreeze {
+            stream: &self.stream,
+            stream_mint: &self.stream_mint,
+            stream_tokens: &self.destination,
+            token_program: &self.token_program,
+        }
+        .freeze()
+    }
+
+    /// Adds the [Stream] to the [Self::creator_index] and the
+    /// [Self::recipient_index].
+    fn index_stream(
+        &mut self,
+        creator_index_bump: u8,
+        creator_entry_bump: u8,
+        recipient_index_bump: u8,
+        recipient_entry_bump: u8,
+    ) -> ProgramResult {
+        let stream = self.stream.key();
+
+        let creator_index = &mut self.creator_index;
+        creator_index.key = self.source_authority.key();
+        creator_index.bump = creator_index_bump;
+        let entry = &mut self.creator_index_entry;
+        entry.index = creator_index.key();
+        entry.position = unwrap_int!(creator_index.next_position());
+        entry.bump = creator_entry_bump;
+        entry.stream = stream;
+
+        let recipient_index = &mut self.recipient_index;
+        recipient_index.key = self.destination.owner;
+        recipient_index.bump = recipient_index_bump;
+        let entry = &mut self.recipient_index_entry;
+        entry.index = recipient_index.key();
+        entry.position = unwrap_int!(recipient_index.next_position());
+        entry.bump = recipient_entry_bump;
+        entry.stream = stream;
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn init_stream(
         &mut self,
         stream_bump: u8,
         start_ts: i64,
         cliff_ts: i64,
+        cliff_amount: u64,
         end_ts: i64,
+        period: i64,
+        breakpoints: &[Breakpoint],
         revoker: Pubkey,
+        auto_redeem: bool,
+        transferable: bool,
+        cancel_until_ts: i64,
+        acceptance_deadline: i64,
+        withdraw_fee_bps: u16,
+        metadata: StreamMetadata,
     ) -> ProgramResult {
         let stream = &mut self.stream;
         stream.mint = self.stream_mint.key();
         stream.bump = stream_bump;
+        stream.version = Stream::VERSION;
+        stream.creator = self.source_authority.key();
+        stream.metadata = metadata;
 
         stream.revoker = revoker;
+        stream.auto_redeem = auto_redeem;
+        if !transferable {
+            stream.beneficiary = self.destination.owner;
+        }
+        stream.cancel_until_ts = cancel_until_ts;
+        if acceptance_deadline != 0 {
+            stream.status = StreamStatus::Pending;
+            stream.acceptance_deadline = acceptance_deadline;
+        }
+        if withdraw_fee_bps > 0 {
+            stream.author_fee_to = self.author_fee_to.key();
+        }
         stream.crate_token = self.crate_token.key();
         stream.underlying_mint = self.underlying_tokens.mint.key();
         stream.underlying_tokens = self.underlying_tokens.key();
@@ -117,70 +300,233 @@ impl<'info> CreateStream<'info> {
 
         stream.start_ts = start_ts;
         stream.cliff_ts = cliff_ts;
+        stream.cliff_amount = cliff_amount;
         stream.end_ts = end_ts;
+        stream.period = period;
+        stream.num_breakpoints = unwrap_int!(breakpoints.len().to_u8());
+        stream.breakpoints[..breakpoints.len()].copy_from_slice(breakpoints);
         Ok(())
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<CreateStream>,
     stream_bump: u8,
     crate_bump: u8,
+    creator_index_bump: u8,
+    creator_entry_bump: u8,
+    recipient_index_bump: u8,
+    recipient_entry_bump: u8,
     start_ts: i64,
     cliff_ts: i64,
+    cliff_amount: u64,
     end_ts: i64,
+    period: i64,
+    breakpoints: Vec<Breakpoint>,
     revoker: Pubkey,
+    auto_redeem: bool,
+    transferable: bool,
+    cancel_until_ts: i64,
+    acceptance_deadline: i64,
+    withdraw_fee_bps: u16,
+    amount: u64,
+    metadata: StreamMetadata,
 ) -> ProgramResult {
-    invariant!(end_ts > start_ts, InvalidSchedule);
-
-    invariant!(cliff_ts >= start_ts);
-    invariant!(cliff_ts <= end_ts);
-
+    if amount > 0 {
+        ctx.accounts.deposit_underlying(amount)?;
+    }
+    // any tokens already held by the Crate are also streamed
     let amount = ctx.accounts.underlying_tokens.amount;
+    invariant!(amount > 0);
+    validate_schedule(
+        start_ts,
+        cliff_ts,
+        cliff_amount,
+        end_ts,
+        period,
+        &breakpoints,
+        amount,
+    )?;
+    // a Stream may not be cancelled, nor held back from redemption, forever
+    invariant!(
+        cancel_until_ts >= 0 && cancel_until_ts <= end_ts,
+        InvalidCancellationWindow
+    );
+    if acceptance_deadline != 0 {
+        // the schedule is relative to the time of acceptance
+        invariant!(start_ts >= 0, InvalidSchedule);
+        invariant!(
+            acceptance_deadline > Clock::get()?.unix_timestamp,
+            InvalidAcceptanceDeadline
+        );
+    }
+    invariant!(withdraw_fee_bps <= MAX_WITHDRAW_FEE_BPS, InvalidWithdrawFee);
+    // the Stream freezes the tokens of a non-transferable Stream
+    let freeze_authority = if transferable {
+        ctx.accounts.crate_token.key()
+    } else {
+        ctx.accounts.stream.key()
+    };
+    assert_keys_eq!(
+        ctx.accounts.stream_mint.freeze_authority.unwrap(),
+        freeze_authority,
+        InvalidFreezeAuthority
+    );
+
     ctx.accounts.init_crate(crate_bump)?;
-    ctx.accounts
-        .init_stream(stream_bump, start_ts, cliff_ts, end_ts, revoker)?;
+    ctx.accounts.init_stream(
+        stream_bump,
+        start_ts,
+        cliff_ts,
+        cliff_amount,
+        end_ts,
+        period,
+        &breakpoints,
+        revoker,
+        auto_redeem,
+        transferable,
+        cancel_until_ts,
+        acceptance_deadline,
+        withdraw_fee_bps,
+        metadata,
+    )?;
+    if withdraw_fee_bps > 0 {
+        ctx.accounts.set_withdraw_fee(withdraw_fee_bps)?;
+    }
+    ctx.accounts.index_stream(
+        creator_index_bump,
+        creator_entry_bump,
+        recipient_index_bump,
+        recipient_entry_bump,
+    )?;
     ctx.accounts.issue_tokens(amount)?;
+    ctx.accounts.freeze_tokens()?;
 
     let stream = &ctx.accounts.stream;
     emit!(StreamCreateEvent {
         stream: stream.key(),
         mint: stream.mint,
+        creator: stream.creator,
         amount: stream.initial_amount,
         start_ts: stream.start_ts,
         cliff_ts: stream.cliff_ts,
+        cliff_amount: stream.cliff_amount,
         end_ts: stream.end_ts,
+        period: stream.period,
+        breakpoints,
+        auto_redeem: stream.auto_redeem,
+        beneficiary: stream.beneficiary,
+        cancel_until_ts: stream.cancel_until_ts,
+        acceptance_deadline: stream.acceptance_deadline,
+        author_fee_to: stream.author_fee_to,
+        withdraw_fee_bps,
+        metadata: stream.metadata,
     });
 
     Ok(())
 }
 
+/// Validates the release schedule of a [Stream] of `amount` tokens.
+pub(crate) fn validate_schedule(
+    start_ts: i64,
+    cliff_ts: i64,
+    cliff_amount: u64,
+    end_ts: i64,
+    period: i64,
+    breakpoints: &[Breakpoint],
+    amount: u64,
+) -> ProgramResult {
+    invariant!(end_ts > start_ts, InvalidSchedule);
+
+    invariant!(cliff_ts >= start_ts);
+    invariant!(cliff_ts <= end_ts);
+
+    invariant!(period >= 0, InvalidPeriod);
+    invariant!(
+        period <= unwrap_int!(end_ts.checked_sub(start_ts)),
+        InvalidPeriod
+    );
+
+    invariant!(cliff_amount <= amount, InvalidCliffAmount);
+    validate_breakpoints(breakpoints, start_ts, end_ts, amount)?;
+    if !breakpoints.is_empty() {
+        // breakpoints replace the linear release
+        invariant!(period == 0, InvalidBreakpoints);
+        invariant!(cliff_amount == 0, InvalidBreakpoints);
+    }
+
+    Ok(())
+}
+
+/// Validates a piecewise-linear release schedule. The breakpoints must be
+/// strictly increasing in time, non-decreasing in amount, and must end with
+/// the full amount of the [Stream] at its end time.
+fn validate_breakpoints(
+    breakpoints: &[Breakpoint],
+    start_ts: i64,
+    end_ts: i64,
+    amount: u64,
+) -> ProgramResult {
+    invariant!(breakpoints.len() <= MAX_BREAKPOINTS, TooManyBreakpoints);
+
+    let (first, last) = match (breakpoints.first(), breakpoints.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Ok(()),
+    };
+    invariant!(first.ts >= start_ts, InvalidBreakpoints);
+    invariant!(last.ts == end_ts, InvalidBreakpoints);
+    invariant!(last.amount == amount, InvalidBreakpoints);
+
+    for window in breakpoints.windows(2) {
+        if let [prev, next] = window {
+            invariant!(next.ts > prev.ts, InvalidBreakpoints);
+            invariant!(next.amount >= prev.amount, InvalidBreakpoints);
+        }
+    }
+
+    Ok(())
+}
+
 #[event]
 pub struct StreamCreateEvent {
     #[index]
     pub stream: Pubkey,
     #[index]
     pub mint: Pubkey,
+    #[index]
+    pub creator: Pubkey,
     pub amount: u64,
     pub start_ts: i64,
     pub cliff_ts: i64,
+    pub cliff_amount: u64,
     pub end_ts: i64,
+    pub period: i64,
+    pub breakpoints: Vec<Breakpoint>,
+    pub auto_redeem: bool,
+    pub beneficiary: Pubkey,
+    pub cancel_until_ts: i64,
+    pub acceptance_deadline: i64,
+    pub author_fee_to: Pubkey,
+    pub withdraw_fee_bps: u16,
+    pub metadata: StreamMetadata,
 }
 
 impl<'info> Validate<'info> for CreateStream<'info> {
     fn validate(&self) -> ProgramResult {
         assert_keys_eq!(self.stream_mint.mint_authority.unwrap(), self.crate_token);
-        assert_keys_eq!(self.stream_mint.freeze_authority.unwrap(), self.crate_token);
         invariant!(self.stream_mint.supply == 0);
 
         assert_keys_eq!(self.underlying_tokens.owner, self.crate_token);
-        invariant!(self.underlying_tokens.amount > 0);
         invariant!(self.underlying_tokens.delegate.is_none());
         invariant!(self.underlying_tokens.close_authority.is_none());
 
         assert_keys_eq!(self.underlying_tokens.mint, self.underlying_mint);
         invariant!(self.underlying_mint.decimals == self.stream_mint.decimals);
 
+        assert_keys_eq!(self.source_tokens.mint, self.underlying_mint);
+        assert_keys_eq!(self.source_tokens.owner, self.source_authority);
+
         Ok(())
     }
 }
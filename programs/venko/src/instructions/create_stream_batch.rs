@@ -0,0 +1,468 @@
+//! Instruction handler for [crate::venko::create_stream_batch].
+
+use crate::*;
+use anchor_lang::solana_program::{
+    program::{invoke, invoke_signed},
+    system_instruction,
+};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, Mint, TokenAccount, Transfer};
+use num_traits::ToPrimitive;
+use vipers::{assert_keys_eq, invariant, unwrap_int, Validate};
+
+/// Number of remaining accounts required for each [StreamLeg].
+pub const ACCOUNTS_PER_LEG: usize = 8;
+
+/// Parameters of a single [Stream] created by [venko::create_stream_batch].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StreamLeg {
+    /// Bump seed of the [Stream].
+    pub stream_bump: u8,
+    /// Bump seed of the [crate_token::CrateToken].
+    pub crate_bump: u8,
+    /// Bump seed of the [StreamIndexEntry] in the creator's [StreamIndex].
+    pub creator_entry_bump: u8,
+    /// Bump seed of the recipient's [StreamIndex].
+    pub recipient_index_bump: u8,
+    /// Bump seed of the [StreamIndexEntry] in the recipient's [StreamIndex].
+    pub recipient_entry_bump: u8,
+    /// Amount of underlying tokens to deposit into the [Stream].
+    pub amount: u64,
+    /// Amount of tokens released at the cliff.
+    pub cliff_amount: u64,
+}
+
+/// Accounts for [venko::create_stream_batch].
+///
+/// Each [StreamLeg] takes [ACCOUNTS_PER_LEG] remaining accounts, in order:
+///
+/// 0. `[writable]` The [Mint] of the [Stream].
+/// 1. `[writable]` The [Stream] account to be created.
+/// 2. `[writable]` The [crate_token::CrateToken] to be created.
+/// 3. `[writable]` The [TokenAccount] holding the [Stream]'s tokens. This
+///    must be the associated token account of the [crate_token::CrateToken],
+///    and must already exist.
+/// 4. `[writable]` Destination of the [Stream] tokens.
+/// 5. `[writable]` The [StreamIndexEntry] to be created in the
+///    [Self::creator_index].
+/// 6. `[writable]` The [StreamIndex] of the owner of the destination, which
+///    is created if it does not exist.
+/// 7. `[writable]` The [StreamIndexEntry] to be created in the recipient's
+///    [StreamIndex].
+#[derive(Accounts)]
+#[instruction(creator_index_bump: u8)]
+pub struct CreateStreamBatch<'info> {
+    /// Underlying mint.
+    pub underlying_mint: Box<Account<'info, Mint>>,
+    /// Source of the underlying tokens of every [Stream].
+    #[account(mut)]
+    pub source_tokens: Box<Account<'info, TokenAccount>>,
+    /// Authority of the [Self::source_tokens].
+    pub source_authority: Signer<'info>,
+    /// The [StreamIndex] of the [Stream]s created by the
+    /// [Self::source_authority]. Created if it does not exist.
+    #[account(
+        init_if_needed,
+        seeds = [
+            b"CreatorIndex",
+            source_authority.key().as_ref()
+        ],
+        bump = creator_index_bump,
+        payer = payer
+    )]
+    pub creator_index: Box<Account<'info, StreamIndex>>,
+    /// Payer for the [Stream] account creation.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+    /// [crate_token] program.
+    pub crate_token_program: Program<'info, crate_token::program::CrateToken>,
+    /// SPL [token] program.
+    pub token_program: Program<'info, token::Token>,
+}
+
+impl<'info> CreateStreamBatch<'info> {
+    /// Creates the [Stream] of a single [StreamLeg].
+    #[allow(clippy::too_many_arguments)]
+    fn create_leg(
+        &mut self,
+        leg: &StreamLeg,
+        accounts: &[AccountInfo<'info>],
+        start_ts: i64,
+        cliff_ts: i64,
+        end_ts: i64,
+        period: i64,
+        revoker: Pubkey,
+        auto_redeem: bool,
+    ) -> ProgramResult {
+        invariant!(accounts.len() == ACCOUNTS_PER_LEG, InvalidBatch);
+        let stream_mint: Account<Mint> = Account::try_from(&accounts[0])?;
+        let stream_info = &accounts[1];
+        let crate_token = &accounts[2];
+        let underlying_tokens: Account<TokenAccount> = Account::try_from(&accounts[3])?;
+        let destination: Account<TokenAccount> = Account::try_from(&accounts[4])?;
+        let creator_entry = &accounts[5];
+        let recipient_index_info = &accounts[6];
+        let recipient_entry = &accounts[7];
+
+        self.validate_leg(&stream_mint, crate_token, &underlying_tokens)?;
+        invariant!(leg.amount > 0, InvalidBatch);
+        let amount = unwrap_int!(underlying_tokens.amount.checked_add(leg.amount));
+        create_stream::validate_schedule(
+            start_ts,
+            cliff_ts,
+            leg.cliff_amount,
+            end_ts,
+            period,
+            &[],
+            amount,
+        )?;
+
+        let mint = stream_mint.key();
+        let mut stream: Account<Stream> =
+            self.create_pda_account(stream_info, &[b"Stream", mint.as_ref(), &[leg.stream_bump]])?;
+        stream.mint = stream_mint.key();
+        stream.bump = leg.stream_bump;
+        stream.version = Stream::VERSION;
+        stream.creator = self.source_authority.key();
+
+        stream.revoker = revoker;
+        stream.auto_redeem = auto_redeem;
+        stream.crate_token = crate_token.key();
+        stream.underlying_mint = self.underlying_mint.key();
+        stream.underlying_tokens = underlying_tokens.key();
+
+        stream.initial_amount = amount;
+        stream.redeemed_amount = 0;
+
+        stream.start_ts = start_ts;
+        stream.cliff_ts = cliff_ts;
+        stream.cliff_amount = leg.cliff_amount;
+        stream.end_ts = end_ts;
+        stream.period = period;
+
+        self.init_crate(&stream_mint, &stream, crate_token, leg.crate_bump)?;
+        self.deposit_underlying(&underlying_tokens, leg.amount)?;
+        self.issue_tokens(&stream_mint, &stream, crate_token, &destination, amount)?;
+        stream.exit(&crate::ID)?;
+
+        let position = unwrap_int!(self.creator_index.next_position());
+        self.create_index_entry(
+            self.creator_index.key(),
+            position,
+            creator_entry,
+            leg.creator_entry_bump,
+            stream.key(),
+        )?;
+
+        let recipient = destination.owner;
+        let mut recipient_index = self.load_or_create_index(
+            recipient_index_info,
+            &[
+                b"RecipientIndex",
+                recipient.as_ref(),
+                &[leg.recipient_index_bump],
+            ],
+        )?;
+        recipient_index.key = recipient;
+        recipient_index.bump = leg.recipient_index_bump;
+        let position = unwrap_int!(recipient_index.next_position());
+        recipient_index.exit(&crate::ID)?;
+        self.create_index_entry(
+            recipient_index.key(),
+            position,
+            recipient_entry,
+            leg.recipient_entry_bump,
+            stream.key(),
+        )?;
+
+        emit!(StreamCreateEvent {
+            stream: stream.key(),
+            mint: stream.mint,
+            creator: stream.creator,
+            amount: stream.initial_amount,
+            start_ts: stream.start_ts,
+            cliff_ts: stream.cliff_ts,
+            cliff_amount: stream.cliff_amount,
+            end_ts: stream.end_ts,
+            period: stream.period,
+            breakpoints: vec![],
+            auto_redeem: stream.auto_redeem,
+            beneficiary: stream.beneficiary,
+            cancel_until_ts: stream.cancel_until_ts,
+            acceptance_deadline: stream.acceptance_deadline,
+            author_fee_to: stream.author_fee_to,
+            withdraw_fee_bps: 0,
+            metadata: stream.metadata,
+        });
+
+        Ok(())
+    }
+
+    /// Creates the [StreamIndexEntry] pointing at a [Stream] from a position
+    /// of a [StreamIndex].
+    fn create_index_entry(
+        &self,
+        index: Pubkey,
+        position: u64,
+        info: &AccountInfo<'info>,
+        bump: u8,
+        stream: Pubkey,
+    ) -> ProgramResult {
+        let mut entry: Account<StreamIndexEntry> = self.create_pda_account(
+            info,
+            &[
+                b"StreamIndexEntry",
+                index.as_ref(),
+                &position.to_le_bytes(),
+                &[bump],
+            ],
+        )?;
+        entry.index = index;
+        entry.position = position;
+        entry.bump = bump;
+        entry.stream = stream;
+        entry.exit(&crate::ID)
+    }
+
+    /// Loads the [StreamIndex] at the address derived from `seeds`, creating
+    /// it if it does not exist.
+    fn load_or_create_index(
+        &self,
+        info: &AccountInfo<'info>,
+        seeds: &[&[u8]],
+    ) -> Result<Account<'info, StreamIndex>> {
+        if info.data_is_empty() {
+            return self.create_pda_account(info, seeds);
+        }
+        assert_program_address(info, seeds)?;
+        Ok(Account::try_from(info)?)
+    }
+
+    /// Creates a program account at the address derived from `seeds`,
+    /// returning it to be initialized.
+    ///
+    /// As with Anchor's `init`, an address which has already been funded is
+    /// topped up to be rent exempt, then allocated and assigned.
+    fn create_pda_account<T>(
+        &self,
+        info: &AccountInfo<'info>,
+        seeds: &[&[u8]],
+    ) -> Result<Account<'info, T>>
+    where
+        T: AccountSerialize + AccountDeserialize + AnchorSerialize + Owner + Clone + Default,
+    {
+        assert_program_address(info, seeds)?;
+
+        let space = 8 + T::default()
+            .try_to_vec()
+            .map_err(|e| ProgramError::BorshIoError(e.to_string()))?
+            .len();
+        let rent_exempt_lamports = Rent::get()?.minimum_balance(space);
+        let current_lamports = info.lamports();
+        if current_lamports == 0 {
+            invoke_signed(
+                &system_instruction::create_account(
+                    self.payer.key,
+                    info.key,
+                    rent_exempt_lamports,
+                    unwrap_int!(space.to_u64()),
+                    &crate::ID,
+                ),
+                &[
+                    self.payer.to_account_info(),
+                    info.clone(),
+                    self.system_program.to_account_info(),
+                ],
+                &[seeds],
+            )?;
+        } else {
+            let required_lamports = rent_exempt_lamports.saturating_sub(current_lamports);
+            if required_lamports > 0 {
+                invoke(
+                    &system_instruction::transfer(self.payer.key, info.key, required_lamports),
+                    &[
+                        self.payer.to_account_info(),
+                        info.clone(),
+                        self.system_program.to_account_info(),
+                    ],
+                )?;
+            }
+            invoke_signed(
+                &system_instruction::allocate(info.key, unwrap_int!(space.to_u64())),
+                &[info.clone(), self.system_program.to_account_info()],
+                &[seeds],
+            )?;
+            invoke_signed(
+                &system_instruction::assign(info.key, &crate::ID),
+                &[info.clone(), self.system_program.to_account_info()],
+                &[seeds],
+            )?;
+        }
+        Ok(Account::try_from_unchecked(info)?)
+    }
+
+    fn init_crate(
+        &self,
+        stream_mint: &Account<'info, Mint>,
+        stream: &Account<'info, Stream>,
+        crate_token: &AccountInfo<'info>,
+        crate_bump: u8,
+    ) -> ProgramResult {
+        crate_token::cpi::new_crate(
+            CpiContext::new(
+                self.crate_token_program.to_account_info(),
+                crate_token::cpi::accounts::NewCrate {
+                    crate_mint: stream_mint.to_account_info(),
+                    crate_token: crate_token.clone(),
+
+                    // no fees, and no fee setter to add them later
+                    fee_to_setter: self.system_program.to_account_info(),
+                    fee_setter_authority: self.system_program.to_account_info(),
+                    author_fee_to: self.system_program.to_account_info(),
+
+                    // authorities
+                    issue_authority: stream.to_account_info(),
+                    withdraw_authority: stream.to_account_info(),
+                    payer: self.payer.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                },
+            ),
+            crate_bump,
+        )
+    }
+
+    fn deposit_underlying(
+        &self,
+        underlying_tokens: &Account<'info, TokenAccount>,
+        amount: u64,
+    ) -> ProgramResult {
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.source_tokens.to_account_info(),
+                    to: underlying_tokens.to_account_info(),
+                    authority: self.source_authority.to_account_info(),
+                },
+            ),
+            amount,
+        )
+    }
+
+    /// Issue the [Stream] tokens.
+    fn issue_tokens(
+        &self,
+        stream_mint: &Account<'info, Mint>,
+        stream: &Account<'info, Stream>,
+        crate_token: &AccountInfo<'info>,
+        destination: &Account<'info, TokenAccount>,
+        amount: u64,
+    ) -> ProgramResult {
+        let signer_seeds: &[&[&[u8]]] = stream_seeds!(stream);
+        crate_token::cpi::issue(
+            CpiContext::new(
+                self.crate_token_program.to_account_info(),
+                crate_token::cpi::accounts::Issue {
+                    crate_mint: stream_mint.to_account_info(),
+                    crate_token: crate_token.clone(),
+
+                    // authorities
+                    issue_authority: stream.to_account_info(),
+
+                    mint_destination: destination.to_account_info(),
+                    author_fee_destination: destination.to_account_info(),
+                    protocol_fee_destination: destination.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds),
+            amount,
+        )
+    }
+
+    /// Validates the accounts of a single [StreamLeg]. These mirror the
+    /// checks of [CreateStream].
+    fn validate_leg(
+        &self,
+        stream_mint: &Account<'info, Mint>,
+        crate_token: &AccountInfo<'info>,
+        underlying_tokens: &Account<'info, TokenAccount>,
+    ) -> ProgramResult {
+        assert_keys_eq!(stream_mint.mint_authority.unwrap(), crate_token);
+        assert_keys_eq!(stream_mint.freeze_authority.unwrap(), crate_token);
+        invariant!(stream_mint.supply == 0);
+
+        assert_keys_eq!(
+            underlying_tokens,
+            get_associated_token_address(crate_token.key, &self.underlying_mint.key())
+        );
+        assert_keys_eq!(underlying_tokens.owner, crate_token);
+        invariant!(underlying_tokens.delegate.is_none());
+        invariant!(underlying_tokens.close_authority.is_none());
+
+        assert_keys_eq!(underlying_tokens.mint, self.underlying_mint);
+        invariant!(self.underlying_mint.decimals == stream_mint.decimals);
+
+        Ok(())
+    }
+}
+
+/// Checks that an account is at the program address derived from `seeds`.
+fn assert_program_address(info: &AccountInfo, seeds: &[&[u8]]) -> ProgramResult {
+    let expected = Pubkey::create_program_address(seeds, &crate::ID)
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+    assert_keys_eq!(info, expected, InvalidBatch);
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateStreamBatch<'info>>,
+    creator_index_bump: u8,
+    start_ts: i64,
+    cliff_ts: i64,
+    end_ts: i64,
+    period: i64,
+    revoker: Pubkey,
+    auto_redeem: bool,
+    legs: Vec<StreamLeg>,
+) -> ProgramResult {
+    invariant!(!legs.is_empty(), InvalidBatch);
+    invariant!(
+        ctx.remaining_accounts.len() == unwrap_int!(legs.len().checked_mul(ACCOUNTS_PER_LEG)),
+        InvalidBatch
+    );
+
+    let creator_index = &mut ctx.accounts.creator_index;
+    creator_index.key = ctx.accounts.source_authority.key();
+    creator_index.bump = creator_index_bump;
+
+    for (leg, accounts) in legs
+        .iter()
+        .zip(ctx.remaining_accounts.chunks(ACCOUNTS_PER_LEG))
+    {
+        ctx.accounts.create_leg(
+            leg,
+            accounts,
+            start_ts,
+            cliff_ts,
+            end_ts,
+            period,
+            revoker,
+            auto_redeem,
+        )?;
+    }
+
+    Ok(())
+}
+
+impl<'info> Validate<'info> for CreateStreamBatch<'info> {
+    fn validate(&self) -> ProgramResult {
+        assert_keys_eq!(self.source_tokens.mint, self.underlying_mint);
+        assert_keys_eq!(self.source_tokens.owner, self.source_authority);
+        Ok(())
+    }
+}
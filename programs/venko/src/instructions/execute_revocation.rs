@@ -0,0 +1,113 @@
+//! Instruction handler for [crate::venko::execute_revocation].
+
+use crate::*;
+use anchor_spl::token::{self, TokenAccount};
+use vipers::{assert_keys_eq, invariant, unwrap_int, Validate};
+
+/// Accounts for [venko::execute_revocation].
+#[derive(Accounts)]
+pub struct ExecuteRevocation<'info> {
+    /// [Stream] account.
+    #[account(mut)]
+    pub stream: Box<Account<'info, VersionedStream>>,
+
+    /// Crate token. Its withdraw fee is lifted to withdraw the unvested
+    /// tokens.
+    #[account(mut)]
+    pub crate_token: Box<Account<'info, crate_token::CrateToken>>,
+
+    /// Underlying tokens of the [Stream].
+    #[account(mut)]
+    pub underlying_tokens: Account<'info, TokenAccount>,
+    /// Account of the [Stream::revoker] which receives the unvested tokens.
+    #[account(mut)]
+    pub destination_tokens: Account<'info, TokenAccount>,
+
+    /// [crate_token] program.
+    pub crate_token_program: Program<'info, crate_token::program::CrateToken>,
+    /// SPL [token] program.
+    pub token_program: Program<'info, token::Token>,
+}
+
+impl<'info> ExecuteRevocation<'info> {
+    /// Withdraws the tokens which had not vested by the scheduled revocation.
+    fn execute_revocation(&mut self) -> Result<u64> {
+        let revoke_ts = self.stream.scheduled_revoke_ts;
+        let vested_unredeemed = unwrap_int!(self.stream.outstanding_released(revoke_ts));
+        let unvested = unwrap_int!(self.underlying_tokens.amount.checked_sub(vested_unredeemed));
+
+        self.withdraw_crate_tokens(unvested)?;
+
+        let stream = &mut self.stream;
+        stream.status = StreamStatus::Revoked;
+        stream.revoked_at = revoke_ts;
+        stream.scheduled_revoke_ts = 0;
+
+        Ok(unvested)
+    }
+
+    fn withdraw_crate_tokens(&self, amount: u64) -> ProgramResult {
+        StreamWithdraw::refund(
+            &self.stream,
+            &self.crate_token,
+            &self.underlying_tokens,
+            &self.destination_tokens,
+            &self.crate_token_program,
+            &self.token_program,
+            amount,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<ExecuteRevocation>) -> ProgramResult {
+    let amount = ctx.accounts.execute_revocation()?;
+
+    let stream = &ctx.accounts.stream;
+    emit!(RevocationExecuteEvent {
+        stream: stream.key(),
+        mint: stream.underlying_mint,
+        revoker: stream.revoker,
+        revoked_at: stream.revoked_at,
+        amount,
+        vested_amount: unwrap_int!(stream.total_released(stream.revoked_at)),
+    });
+
+    Ok(())
+}
+
+/// Emitted on [crate::venko::execute_revocation].
+#[event]
+pub struct RevocationExecuteEvent {
+    /// The [Stream].
+    #[index]
+    pub stream: Pubkey,
+    /// Mint of the underlying token.
+    #[index]
+    pub mint: Pubkey,
+    /// The [Stream::revoker].
+    pub revoker: Pubkey,
+    /// The time at which the [Stream] stopped releasing tokens.
+    pub revoked_at: i64,
+
+    /// Unvested tokens sent to the revoker.
+    pub amount: u64,
+    /// Total tokens vested by the [Stream], including those already redeemed.
+    pub vested_amount: u64,
+}
+
+impl<'info> Validate<'info> for ExecuteRevocation<'info> {
+    fn validate(&self) -> ProgramResult {
+        self.stream.assert_active()?;
+        invariant!(self.stream.scheduled_revoke_ts != 0, RevocationNotScheduled);
+        invariant!(
+            self.stream.scheduled_revoke_ts <= Clock::get()?.unix_timestamp,
+            RevocationPending
+        );
+
+        assert_keys_eq!(self.crate_token, self.stream.crate_token);
+        assert_keys_eq!(self.underlying_tokens, self.stream.underlying_tokens);
+        assert_keys_eq!(self.destination_tokens.owner, self.stream.revoker);
+        assert_keys_eq!(self.destination_tokens.mint, self.stream.underlying_mint);
+        Ok(())
+    }
+}
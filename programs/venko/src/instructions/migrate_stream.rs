@@ -0,0 +1,81 @@
+//! Instruction handler for [crate::venko::migrate_stream].
+
+use crate::*;
+use anchor_lang::solana_program::{program::invoke, system_instruction};
+use anchor_lang::Discriminator;
+use vipers::{assert_keys_eq, invariant, Validate};
+
+/// Accounts for [venko::migrate_stream].
+#[derive(Accounts)]
+pub struct MigrateStream<'info> {
+    /// Version 0 [Stream] account, with the [StreamV0] layout. This cannot be
+    /// deserialized as a [Stream] until it has been migrated.
+    #[account(mut)]
+    pub stream: UncheckedAccount<'info>,
+
+    /// Payer for the rent of the additional space.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateStream>) -> ProgramResult {
+    let stream_info = ctx.accounts.stream.to_account_info();
+    let legacy = StreamV0::try_from_slice(&stream_info.try_borrow_data()?[8..])?;
+
+    let rent_lamports = Rent::get()?
+        .minimum_balance(Stream::LEN)
+        .saturating_sub(stream_info.lamports());
+    if rent_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(ctx.accounts.payer.key, stream_info.key, rent_lamports),
+            &[
+                ctx.accounts.payer.to_account_info(),
+                stream_info.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    // fields were added throughout the layout, so the account is rewritten
+    stream_info.realloc(Stream::LEN, true)?;
+
+    let stream = legacy.migrate();
+    {
+        let mut data = stream_info.try_borrow_mut_data()?;
+        let dst: &mut [u8] = &mut data;
+        stream.try_serialize(&mut std::io::Cursor::new(dst))?;
+    }
+
+    emit!(StreamMigrateEvent {
+        stream: stream_info.key(),
+        version: stream.version,
+    });
+
+    Ok(())
+}
+
+/// Emitted on [crate::venko::migrate_stream].
+#[event]
+pub struct StreamMigrateEvent {
+    /// The [Stream].
+    #[index]
+    pub stream: Pubkey,
+    /// The new [Stream::version].
+    pub version: u8,
+}
+
+impl<'info> Validate<'info> for MigrateStream<'info> {
+    fn validate(&self) -> ProgramResult {
+        assert_keys_eq!(*self.stream.owner, crate::ID);
+        invariant!(
+            self.stream.data_len() == StreamV0::LEN,
+            StreamAlreadyMigrated
+        );
+        if self.stream.try_borrow_data()?[..8] != Stream::discriminator() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+}
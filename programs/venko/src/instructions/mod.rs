@@ -1,9 +1,56 @@
 //! Instructions for Venko.
 
+pub mod accept_revoker;
+pub mod accept_stream;
+pub mod cancel_revocation;
+pub mod cancel_stream;
+pub mod claim_vested;
+pub mod close_stream;
+pub mod crank_redeem;
+pub mod create_claim_status;
+pub mod create_holder_claim;
 pub mod create_stream;
+pub mod create_stream_batch;
+pub mod execute_revocation;
+pub mod migrate_stream;
+pub mod new_vesting_distributor;
+pub mod pause;
 pub mod redeem;
+pub mod redeem_max;
+pub mod reject_stream;
+pub mod renounce_revoker;
+pub mod resume;
 pub mod revoke;
+pub mod revoke_unvested;
+pub mod schedule_revocation;
+pub mod thaw_stream_tokens;
+pub mod top_up;
+pub mod transfer_revoker;
+pub mod update_stream_metadata;
 
+pub use accept_revoker::*;
+pub use accept_stream::*;
+pub use cancel_revocation::*;
+pub use cancel_stream::*;
+pub use claim_vested::*;
+pub use close_stream::*;
+pub use crank_redeem::*;
+pub use create_claim_status::*;
+pub use create_holder_claim::*;
 pub use create_stream::*;
+pub use create_stream_batch::*;
+pub use execute_revocation::*;
+pub use migrate_stream::*;
+pub use new_vesting_distributor::*;
+pub use pause::*;
 pub use redeem::*;
+pub use reject_stream::*;
+pub use renounce_revoker::*;
+pub use resume::*;
 pub use revoke::*;
+pub use revoke_unvested::*;
+pub use schedule_revocation::*;
+pub use thaw_stream_tokens::*;
+pub use top_up::*;
+pub use transfer_revoker::*;
+pub use update_stream_metadata::*;
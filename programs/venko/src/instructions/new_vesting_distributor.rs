@@ -0,0 +1,103 @@
+//! Instruction handler for [crate::venko::new_vesting_distributor].
+
+use crate::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use vipers::{assert_keys_eq, invariant, Validate};
+
+/// Accounts for [venko::new_vesting_distributor].
+#[derive(Accounts)]
+#[instruction(bump: u8)]
+pub struct NewVestingDistributor<'info> {
+    /// Base key of the [VestingDistributor].
+    pub base: Signer<'info>,
+    /// [VestingDistributor] account.
+    #[account(
+        init,
+        seeds = [
+            b"VestingDistributor",
+            base.key().as_ref()
+        ],
+        bump = bump,
+        payer = payer
+    )]
+    pub distributor: Account<'info, VestingDistributor>,
+    /// Underlying mint.
+    pub underlying_mint: Account<'info, Mint>,
+    /// The [TokenAccount] holding the tokens to distribute.
+    /// Must be owned by the [Self::distributor].
+    pub underlying_tokens: Account<'info, TokenAccount>,
+
+    /// Payer for the [VestingDistributor] account creation.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler(
+    ctx: Context<NewVestingDistributor>,
+    bump: u8,
+    root: [u8; 32],
+    max_total_claim: u64,
+    start_ts: i64,
+    cliff_ts: i64,
+    end_ts: i64,
+    period: i64,
+) -> ProgramResult {
+    invariant!(max_total_claim > 0);
+    create_stream::validate_schedule(start_ts, cliff_ts, 0, end_ts, period, &[], max_total_claim)?;
+
+    let distributor = &mut ctx.accounts.distributor;
+    distributor.base = ctx.accounts.base.key();
+    distributor.bump = bump;
+
+    distributor.root = root;
+    distributor.underlying_mint = ctx.accounts.underlying_mint.key();
+    distributor.underlying_tokens = ctx.accounts.underlying_tokens.key();
+
+    distributor.max_total_claim = max_total_claim;
+    distributor.total_redeemed = 0;
+
+    distributor.start_ts = start_ts;
+    distributor.cliff_ts = cliff_ts;
+    distributor.end_ts = end_ts;
+    distributor.period = period;
+
+    emit!(VestingDistributorCreateEvent {
+        distributor: distributor.key(),
+        base: distributor.base,
+        root,
+        max_total_claim,
+        start_ts,
+        cliff_ts,
+        end_ts,
+        period,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct VestingDistributorCreateEvent {
+    #[index]
+    pub distributor: Pubkey,
+    #[index]
+    pub base: Pubkey,
+    pub root: [u8; 32],
+    pub max_total_claim: u64,
+    pub start_ts: i64,
+    pub cliff_ts: i64,
+    pub end_ts: i64,
+    pub period: i64,
+}
+
+impl<'info> Validate<'info> for NewVestingDistributor<'info> {
+    fn validate(&self) -> ProgramResult {
+        assert_keys_eq!(self.underlying_tokens.owner, self.distributor);
+        assert_keys_eq!(self.underlying_tokens.mint, self.underlying_mint);
+        invariant!(self.underlying_tokens.delegate.is_none());
+        invariant!(self.underlying_tokens.close_authority.is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+//! Instruction handler for [crate::venko::pause].
+
+use crate::*;
+use vipers::{assert_keys_eq, invariant, Validate};
+
+/// Accounts for [venko::pause].
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    /// [Stream] account.
+    #[account(mut)]
+    pub stream: Box<Account<'info, VersionedStream>>,
+    /// The [Stream::revoker].
+    pub revoker: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<Pause>) -> ProgramResult {
+    let stream = &mut ctx.accounts.stream;
+    invariant!(stream.paused_at == 0, Paused);
+    let now = Clock::get()?.unix_timestamp;
+    // nothing is released after a scheduled revocation anyway
+    invariant!(
+        stream.scheduled_revoke_ts == 0 || now < stream.scheduled_revoke_ts,
+        RevocationPassed
+    );
+    stream.paused_at = now;
+
+    emit!(StreamPauseEvent {
+        stream: stream.key(),
+        revoker: ctx.accounts.revoker.key(),
+        paused_at: stream.paused_at,
+    });
+
+    Ok(())
+}
+
+/// Emitted on [crate::venko::pause].
+#[event]
+pub struct StreamPauseEvent {
+    /// The [Stream].
+    #[index]
+    pub stream: Pubkey,
+    /// The [Stream::revoker].
+    pub revoker: Pubkey,
+    /// The time at which the [Stream] was paused.
+    pub paused_at: i64,
+}
+
+impl<'info> Validate<'info> for Pause<'info> {
+    fn validate(&self) -> ProgramResult {
+        invariant!(self.stream.revoker != Pubkey::default(), Irrevocable);
+        assert_keys_eq!(self.stream.revoker, self.revoker, NotRevoker);
+        self.stream.assert_active()?;
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 //! Instruction handler for [crate::venko::redeem].
 
 use crate::*;
+use anchor_lang::solana_program::program_option::COption;
 use anchor_spl::token::{self, Burn, Mint, TokenAccount};
 use vipers::{assert_keys_eq, invariant, unwrap_int, Validate};
 
@@ -13,12 +14,15 @@ pub struct Redeem<'info> {
     pub stream_mint: Account<'info, Mint>,
     /// [Stream] account.
     #[account(mut)]
-    pub stream: Box<Account<'info, Stream>>,
+    pub stream: Box<Account<'info, VersionedStream>>,
 
     /// The [TokenAccount] holding the [Self::user_authority]'s
     /// stream tokens.
     #[account(mut)]
     pub source_stream_tokens: Account<'info, TokenAccount>,
+    /// The [HolderClaim] of the owner of the [Self::source_stream_tokens].
+    #[account(mut)]
+    pub holder_claim: Box<Account<'info, HolderClaim>>,
 
     /// Underlying tokens of the [Stream].
     #[account(mut)]
@@ -26,11 +30,19 @@ pub struct Redeem<'info> {
     /// Destination of the underlying tokens backing the [Stream].
     #[account(mut)]
     pub destination_tokens: Account<'info, TokenAccount>,
+    /// Destination of the author fees charged by the Crate. Must be owned by
+    /// the [Stream::author_fee_to] if the [Stream] charges fees.
+    #[account(mut)]
+    pub author_fee_destination: Account<'info, TokenAccount>,
+    /// Destination of the protocol fees charged by the Crate.
+    #[account(mut)]
+    pub protocol_fee_destination: Account<'info, TokenAccount>,
 
     /// The [crate_token::CrateToken].
     pub crate_token: Box<Account<'info, crate_token::CrateToken>>,
 
-    /// User redeeming the tokens.
+    /// User redeeming the tokens. This is either the owner of the
+    /// [Self::source_stream_tokens] or its approved delegate.
     pub user_authority: Signer<'info>,
 
     /// [System] program.
@@ -42,14 +54,40 @@ pub struct Redeem<'info> {
 }
 
 impl<'info> Redeem<'info> {
-    fn amount_released(&self) -> Result<u64> {
-        let amount_released = unwrap_int!(self
-            .stream
-            .available_for_withdrawal(Clock::get()?.unix_timestamp, self.underlying_tokens.amount));
+    /// Computes the amount of underlying tokens currently available to the
+    /// holder of the [Self::source_stream_tokens].
+    pub(crate) fn amount_released(&self) -> Result<u64> {
+        let amount_released = unwrap_int!(self.stream.holder_available_for_withdrawal(
+            Clock::get()?.unix_timestamp,
+            self.underlying_tokens.amount,
+            self.source_stream_tokens.amount,
+            self.holder_claim.redeemed_amount,
+        ));
         Ok(amount_released)
     }
 
-    fn redeem(&self, amount: u64) -> ProgramResult {
+    /// Returns true if the [Self::user_authority] is the approved delegate of
+    /// the [Self::source_stream_tokens] rather than its owner.
+    fn is_delegate(&self) -> bool {
+        self.source_stream_tokens.owner != self.user_authority.key()
+            && self.source_stream_tokens.delegate == COption::Some(self.user_authority.key())
+    }
+
+    /// Computes the amount of [Stream] tokens the [Self::user_authority] may
+    /// burn from the [Self::source_stream_tokens].
+    pub(crate) fn amount_burnable(&self) -> u64 {
+        if self.is_delegate() {
+            self.source_stream_tokens
+                .delegated_amount
+                .min(self.source_stream_tokens.amount)
+        } else {
+            self.source_stream_tokens.amount
+        }
+    }
+
+    /// Redeems `amount` [Stream] tokens, returning the fees charged by the
+    /// Crate.
+    fn redeem(&self, amount: u64) -> Result<crate_token::state::Fees> {
         let amount_released = self.amount_released()?;
 
         // Has the given amount released?
@@ -57,12 +95,23 @@ impl<'info> Redeem<'info> {
 
         // redeem the crate tokens
         self.burn_stream_tokens(amount)?;
-        self.withdraw_crate_tokens(amount)?;
-
-        Ok(())
+        self.withdraw_crate_tokens(amount)
     }
 
+    /// Burns `amount` [Stream] tokens, thawing the [Self::source_stream_tokens]
+    /// for the burn if they are frozen.
     fn burn_stream_tokens(&self, amount: u64) -> ProgramResult {
+        let remaining_amount = unwrap_int!(self.source_stream_tokens.amount.checked_sub(amount));
+        StreamTokensFreeze {
+            stream: &self.stream,
+            stream_mint: &self.stream_mint,
+            stream_tokens: &self.source_stream_tokens,
+            token_program: &self.token_program,
+        }
+        .with_thawed(remaining_amount, || self.burn(amount))
+    }
+
+    fn burn(&self, amount: u64) -> ProgramResult {
         token::burn(
             CpiContext::new(
                 self.token_program.to_account_info(),
@@ -76,32 +125,29 @@ impl<'info> Redeem<'info> {
         )
     }
 
-    fn withdraw_crate_tokens(&self, amount: u64) -> ProgramResult {
-        let signer_seeds: &[&[&[u8]]] = stream_seeds!(self.stream);
-        crate_token::cpi::withdraw(
-            CpiContext::new_with_signer(
-                self.crate_token_program.to_account_info(),
-                crate_token::cpi::accounts::Withdraw {
-                    crate_token: self.crate_token.to_account_info(),
-                    crate_underlying: self.underlying_tokens.to_account_info(),
-                    withdraw_authority: self.stream.to_account_info(),
-                    withdraw_destination: self.destination_tokens.to_account_info(),
-                    author_fee_destination: self.destination_tokens.to_account_info(),
-                    protocol_fee_destination: self.destination_tokens.to_account_info(),
-                    token_program: self.token_program.to_account_info(),
-                },
-                signer_seeds,
-            ),
-            amount,
-        )
+    fn withdraw_crate_tokens(&self, amount: u64) -> Result<crate_token::state::Fees> {
+        StreamWithdraw {
+            stream: &self.stream,
+            crate_token: &self.crate_token,
+            underlying_tokens: &self.underlying_tokens,
+            destination_tokens: &self.destination_tokens,
+            author_fee_destination: &self.author_fee_destination,
+            protocol_fee_destination: &self.protocol_fee_destination,
+            crate_token_program: &self.crate_token_program,
+            token_program: &self.token_program,
+        }
+        .withdraw(amount)
     }
 }
 
 pub fn handler(ctx: Context<Redeem>, amount: u64) -> ProgramResult {
-    ctx.accounts.redeem(amount)?;
+    let fees = ctx.accounts.redeem(amount)?;
+
+    let holder_claim = &mut ctx.accounts.holder_claim;
+    holder_claim.redeemed_amount = unwrap_int!(holder_claim.redeemed_amount.checked_add(amount));
 
     let stream = &mut ctx.accounts.stream;
-    stream.redeemed_amount = unwrap_int!(stream.redeemed_amount.checked_add(amount));
+    unwrap_int!(stream.record_redemption(amount));
 
     let amount_remaining = unwrap_int!(stream.initial_amount.checked_sub(stream.redeemed_amount));
 
@@ -110,6 +156,8 @@ pub fn handler(ctx: Context<Redeem>, amount: u64) -> ProgramResult {
         mint: stream.underlying_mint,
         amount,
         amount_remaining,
+        author_fee: fees.author_fee,
+        protocol_fee: fees.protocol_fee,
     });
 
     Ok(())
@@ -129,23 +177,55 @@ pub struct RedeemEvent {
     pub amount: u64,
     /// Total tokens remaining
     pub amount_remaining: u64,
+    /// Fees paid to the [Stream::author_fee_to].
+    pub author_fee: u64,
+    /// Fees paid to the Crate protocol.
+    pub protocol_fee: u64,
 }
 
 impl<'info> Validate<'info> for Redeem<'info> {
     fn validate(&self) -> ProgramResult {
         assert_keys_eq!(self.stream_mint, self.stream.mint);
+        self.stream.assert_redeemable()?;
+        invariant!(
+            !self.stream.is_cancellable(Clock::get()?.unix_timestamp),
+            CancellationWindowOpen
+        );
 
-        assert_keys_eq!(self.source_stream_tokens.owner, self.user_authority);
+        if self.is_delegate() {
+            invariant!(
+                self.source_stream_tokens.delegated_amount > 0,
+                InvalidRedeemAuthority
+            );
+        } else {
+            assert_keys_eq!(
+                self.source_stream_tokens.owner,
+                self.user_authority,
+                InvalidRedeemAuthority
+            );
+        }
+        if !self.stream.is_transferable() {
+            invariant!(!self.is_delegate(), NotBeneficiary);
+            assert_keys_eq!(
+                self.source_stream_tokens.owner,
+                self.stream.beneficiary,
+                NotBeneficiary
+            );
+        }
         assert_keys_eq!(self.source_stream_tokens.mint, self.stream.mint);
         invariant!(
             self.source_stream_tokens.amount > 0,
             InsufficientStreamTokens
         );
+        assert_keys_eq!(self.holder_claim.stream, self.stream);
+        assert_keys_eq!(self.holder_claim.owner, self.source_stream_tokens.owner);
 
         assert_keys_eq!(self.underlying_tokens, self.stream.underlying_tokens);
         assert_keys_eq!(self.destination_tokens.mint, self.stream.underlying_mint);
 
         assert_keys_eq!(self.crate_token, self.stream.crate_token);
+        self.stream
+            .assert_author_fee_owner(self.author_fee_destination.owner)?;
         Ok(())
     }
 }
@@ -0,0 +1,14 @@
+//! Instruction handler for [crate::venko::redeem_max].
+
+use crate::*;
+use vipers::invariant;
+
+pub fn handler(ctx: Context<Redeem>) -> ProgramResult {
+    let amount = ctx
+        .accounts
+        .amount_released()?
+        .min(ctx.accounts.amount_burnable());
+    invariant!(amount > 0, InsufficientWithdrawalBalance);
+
+    instructions::redeem::handler(ctx, amount)
+}
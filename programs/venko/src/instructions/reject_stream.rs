@@ -0,0 +1,138 @@
+//! Instruction handler for [crate::venko::reject_stream].
+
+use crate::*;
+use anchor_spl::token::{self, Burn, Mint, TokenAccount};
+use vipers::{assert_keys_eq, invariant, Validate};
+
+/// Accounts for [venko::reject_stream].
+#[derive(Accounts)]
+pub struct RejectStream<'info> {
+    /// [token::Mint] of the [Stream].
+    /// This account is `mut` because tokens are burned.
+    #[account(mut)]
+    pub stream_mint: Account<'info, Mint>,
+    /// [Stream] account.
+    #[account(mut)]
+    pub stream: Box<Account<'info, VersionedStream>>,
+
+    /// The [TokenAccount] holding the entire supply of [Stream] tokens.
+    #[account(mut)]
+    pub source_stream_tokens: Account<'info, TokenAccount>,
+    /// Owner of the [Self::source_stream_tokens].
+    pub holder: Signer<'info>,
+
+    /// Crate token. Its withdraw fee is lifted to return the underlying
+    /// tokens.
+    #[account(mut)]
+    pub crate_token: Box<Account<'info, crate_token::CrateToken>>,
+    /// Underlying tokens of the [Stream].
+    #[account(mut)]
+    pub underlying_tokens: Account<'info, TokenAccount>,
+    /// Token account of the [Stream::creator] which receives the underlying
+    /// tokens.
+    #[account(mut)]
+    pub destination_tokens: Account<'info, TokenAccount>,
+
+    /// [crate_token] program.
+    pub crate_token_program: Program<'info, crate_token::program::CrateToken>,
+    /// SPL [token] program.
+    pub token_program: Program<'info, token::Token>,
+}
+
+impl<'info> RejectStream<'info> {
+    /// Burns all of the [Self::source_stream_tokens], thawing them for the
+    /// burn if they are frozen.
+    fn burn_stream_tokens(&self) -> ProgramResult {
+        StreamTokensFreeze {
+            stream: &self.stream,
+            stream_mint: &self.stream_mint,
+            stream_tokens: &self.source_stream_tokens,
+            token_program: &self.token_program,
+        }
+        .with_thawed(0, || self.burn())
+    }
+
+    fn burn(&self) -> ProgramResult {
+        token::burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.stream_mint.to_account_info(),
+                    to: self.source_stream_tokens.to_account_info(),
+                    authority: self.holder.to_account_info(),
+                },
+            ),
+            self.source_stream_tokens.amount,
+        )
+    }
+
+    /// Withdraws all underlying tokens to the [Self::destination_tokens].
+    fn return_underlying(&self) -> Result<u64> {
+        let amount = self.underlying_tokens.amount;
+        StreamWithdraw::refund(
+            &self.stream,
+            &self.crate_token,
+            &self.underlying_tokens,
+            &self.destination_tokens,
+            &self.crate_token_program,
+            &self.token_program,
+            amount,
+        )?;
+        Ok(amount)
+    }
+}
+
+pub fn handler(ctx: Context<RejectStream>) -> ProgramResult {
+    ctx.accounts.burn_stream_tokens()?;
+    let amount = ctx.accounts.return_underlying()?;
+
+    let stream = &mut ctx.accounts.stream;
+    stream.status = StreamStatus::Rejected;
+
+    emit!(StreamRejectedEvent {
+        stream: stream.key(),
+        mint: stream.underlying_mint,
+        holder: ctx.accounts.holder.key(),
+        creator: stream.creator,
+        amount,
+    });
+
+    Ok(())
+}
+
+/// Emitted on [crate::venko::reject_stream].
+#[event]
+pub struct StreamRejectedEvent {
+    /// The [Stream].
+    #[index]
+    pub stream: Pubkey,
+    /// Mint of the underlying token.
+    #[index]
+    pub mint: Pubkey,
+    /// The holder who rejected the [Stream].
+    pub holder: Pubkey,
+    /// The [Stream::creator], who received the underlying tokens.
+    pub creator: Pubkey,
+    /// Amount of underlying tokens returned.
+    pub amount: u64,
+}
+
+impl<'info> Validate<'info> for RejectStream<'info> {
+    fn validate(&self) -> ProgramResult {
+        assert_keys_eq!(self.stream_mint, self.stream.mint);
+        self.stream.assert_not_finished()?;
+
+        assert_keys_eq!(self.source_stream_tokens.mint, self.stream.mint);
+        assert_keys_eq!(self.source_stream_tokens.owner, self.holder);
+        invariant!(
+            self.source_stream_tokens.amount == self.stream_mint.supply,
+            NotSoleHolder
+        );
+
+        assert_keys_eq!(self.crate_token, self.stream.crate_token);
+        assert_keys_eq!(self.underlying_tokens, self.stream.underlying_tokens);
+        assert_keys_eq!(self.destination_tokens.mint, self.stream.underlying_mint);
+        assert_keys_eq!(self.destination_tokens.owner, self.stream.creator);
+        Ok(())
+    }
+}
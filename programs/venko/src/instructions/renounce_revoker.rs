@@ -0,0 +1,46 @@
+//! Instruction handler for [crate::venko::renounce_revoker].
+
+use crate::*;
+use vipers::invariant;
+
+pub fn handler(ctx: Context<TransferRevoker>) -> ProgramResult {
+    let now = Clock::get()?.unix_timestamp;
+    let stream = &mut ctx.accounts.stream;
+
+    // a paused stream would otherwise never be resumed
+    invariant!(stream.paused_at == 0, Paused);
+    // a revocation which has taken effect must be executed first
+    invariant!(
+        stream.scheduled_revoke_ts == 0 || stream.scheduled_revoke_ts > now,
+        RevocationPassed
+    );
+
+    let revoke_ts = stream.scheduled_revoke_ts;
+    stream.revoker = Pubkey::default();
+    stream.pending_revoker = Pubkey::default();
+    stream.scheduled_revoke_ts = 0;
+
+    if revoke_ts != 0 {
+        emit!(RevocationCancelEvent {
+            stream: stream.key(),
+            revoker: ctx.accounts.revoker.key(),
+            revoke_ts,
+        });
+    }
+    emit!(RevokerRenounceEvent {
+        stream: stream.key(),
+        revoker: ctx.accounts.revoker.key(),
+    });
+
+    Ok(())
+}
+
+/// Emitted on [crate::venko::renounce_revoker].
+#[event]
+pub struct RevokerRenounceEvent {
+    /// The [Stream].
+    #[index]
+    pub stream: Pubkey,
+    /// The [Stream::revoker] which renounced the role.
+    pub revoker: Pubkey,
+}
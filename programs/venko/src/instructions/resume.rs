@@ -0,0 +1,34 @@
+//! Instruction handler for [crate::venko::resume].
+
+use crate::*;
+use vipers::{invariant, unwrap_int};
+
+pub fn handler(ctx: Context<Pause>) -> ProgramResult {
+    let stream = &mut ctx.accounts.stream;
+    invariant!(stream.paused_at != 0, NotPaused);
+
+    let pause_duration = unwrap_int!(stream.resume(Clock::get()?.unix_timestamp));
+
+    emit!(StreamResumeEvent {
+        stream: stream.key(),
+        revoker: ctx.accounts.revoker.key(),
+        pause_duration,
+        paused_duration: stream.paused_duration,
+    });
+
+    Ok(())
+}
+
+/// Emitted on [crate::venko::resume].
+#[event]
+pub struct StreamResumeEvent {
+    /// The [Stream].
+    #[index]
+    pub stream: Pubkey,
+    /// The [Stream::revoker].
+    pub revoker: Pubkey,
+    /// Seconds spent in the pause which just ended.
+    pub pause_duration: i64,
+    /// Total seconds the [Stream] has spent paused.
+    pub paused_duration: i64,
+}
@@ -9,9 +9,11 @@ use vipers::{assert_keys_eq, invariant, Validate};
 pub struct Revoke<'info> {
     /// [Stream] account.
     #[account(mut)]
-    pub stream: Box<Account<'info, Stream>>,
+    pub stream: Box<Account<'info, VersionedStream>>,
 
-    /// Crate token.
+    /// Crate token. Its withdraw fee is lifted to withdraw the unreleased
+    /// tokens.
+    #[account(mut)]
     pub crate_token: Box<Account<'info, crate_token::CrateToken>>,
 
     /// Underlying tokens of the [Stream].
@@ -33,33 +35,26 @@ pub struct Revoke<'info> {
 impl<'info> Revoke<'info> {
     fn revoke(&mut self) -> ProgramResult {
         // redeem the crate tokens
-        self.withdraw_all_crate_tokens()?;
+        self.withdraw_crate_tokens(self.underlying_tokens.amount)?;
 
-        // invalidate the stream
+        // stop releasing tokens, keeping the original schedule
         let stream = &mut self.stream;
-        stream.redeemed_amount = stream.initial_amount;
-        stream.end_ts = Clock::get()?.unix_timestamp;
+        stream.status = StreamStatus::Revoked;
+        stream.revoked_at = Clock::get()?.unix_timestamp;
 
         Ok(())
     }
 
-    fn withdraw_all_crate_tokens(&self) -> ProgramResult {
-        let signer_seeds: &[&[&[u8]]] = stream_seeds!(self.stream);
-        crate_token::cpi::withdraw(
-            CpiContext::new_with_signer(
-                self.crate_token_program.to_account_info(),
-                crate_token::cpi::accounts::Withdraw {
-                    crate_token: self.crate_token.to_account_info(),
-                    crate_underlying: self.underlying_tokens.to_account_info(),
-                    withdraw_authority: self.stream.to_account_info(),
-                    withdraw_destination: self.destination_tokens.to_account_info(),
-                    author_fee_destination: self.destination_tokens.to_account_info(),
-                    protocol_fee_destination: self.destination_tokens.to_account_info(),
-                    token_program: self.token_program.to_account_info(),
-                },
-                signer_seeds,
-            ),
-            self.underlying_tokens.amount,
+    /// Withdraws underlying tokens to the [Self::destination_tokens].
+    pub(crate) fn withdraw_crate_tokens(&self, amount: u64) -> ProgramResult {
+        StreamWithdraw::refund(
+            &self.stream,
+            &self.crate_token,
+            &self.underlying_tokens,
+            &self.destination_tokens,
+            &self.crate_token_program,
+            &self.token_program,
+            amount,
         )
     }
 }
@@ -72,6 +67,7 @@ pub fn handler(ctx: Context<Revoke>) -> ProgramResult {
         stream: stream.key(),
         mint: stream.underlying_mint,
         revoker: ctx.accounts.revoker.key(),
+        revoked_at: stream.revoked_at,
     });
 
     Ok(())
@@ -88,12 +84,15 @@ pub struct RevokeEvent {
     pub mint: Pubkey,
     /// Account that revoked the [Stream].
     pub revoker: Pubkey,
+    /// The time at which the [Stream] was revoked.
+    pub revoked_at: i64,
 }
 
 impl<'info> Validate<'info> for Revoke<'info> {
     fn validate(&self) -> ProgramResult {
         invariant!(self.stream.revoker != Pubkey::default(), Irrevocable);
         assert_keys_eq!(self.stream.revoker, self.revoker, NotRevoker);
+        self.stream.assert_active()?;
 
         assert_keys_eq!(self.crate_token, self.stream.crate_token);
         assert_keys_eq!(self.underlying_tokens, self.stream.underlying_tokens);
@@ -0,0 +1,55 @@
+//! Instruction handler for [crate::venko::revoke_unvested].
+
+use crate::*;
+use vipers::unwrap_int;
+
+impl<'info> Revoke<'info> {
+    /// Withdraws the unvested tokens, leaving everything vested as of now
+    /// claimable by the [Stream] token holders.
+    fn revoke_unvested(&mut self) -> Result<u64> {
+        let now = Clock::get()?.unix_timestamp;
+        let vested_unredeemed = unwrap_int!(self.stream.outstanding_released(now));
+        let unvested = unwrap_int!(self.underlying_tokens.amount.checked_sub(vested_unredeemed));
+
+        self.withdraw_crate_tokens(unvested)?;
+
+        // stop releasing tokens
+        self.stream.status = StreamStatus::Revoked;
+        self.stream.revoked_at = now;
+
+        Ok(unvested)
+    }
+}
+
+pub fn handler(ctx: Context<Revoke>) -> ProgramResult {
+    let amount = ctx.accounts.revoke_unvested()?;
+
+    let stream = &ctx.accounts.stream;
+    emit!(RevokeUnvestedEvent {
+        stream: stream.key(),
+        mint: stream.underlying_mint,
+        revoker: ctx.accounts.revoker.key(),
+        amount,
+        vested_amount: unwrap_int!(stream.total_released(stream.revoked_at)),
+    });
+
+    Ok(())
+}
+
+/// Emitted on [crate::venko::revoke_unvested].
+#[event]
+pub struct RevokeUnvestedEvent {
+    /// The [Stream].
+    #[index]
+    pub stream: Pubkey,
+    /// Mint of the underlying token.
+    #[index]
+    pub mint: Pubkey,
+    /// Account that revoked the [Stream].
+    pub revoker: Pubkey,
+
+    /// Unvested tokens sent to the revoker.
+    pub amount: u64,
+    /// Total tokens vested by the [Stream], including those already redeemed.
+    pub vested_amount: u64,
+}
@@ -0,0 +1,63 @@
+//! Instruction handler for [crate::venko::schedule_revocation].
+
+use crate::*;
+use vipers::{assert_keys_eq, invariant, Validate};
+
+/// Accounts for [venko::schedule_revocation].
+#[derive(Accounts)]
+pub struct ScheduleRevocation<'info> {
+    /// [Stream] account.
+    #[account(mut)]
+    pub stream: Box<Account<'info, VersionedStream>>,
+    /// The [Stream::revoker].
+    pub revoker: Signer<'info>,
+}
+
+impl<'info> ScheduleRevocation<'info> {
+    /// Returns true if the scheduled revocation has taken effect.
+    pub(crate) fn scheduled_revocation_passed(&self, now: i64) -> bool {
+        self.stream.scheduled_revoke_ts != 0 && self.stream.scheduled_revoke_ts <= now
+    }
+}
+
+pub fn handler(ctx: Context<ScheduleRevocation>, revoke_ts: i64) -> ProgramResult {
+    let now = Clock::get()?.unix_timestamp;
+    invariant!(revoke_ts >= now, InvalidRevocationTime);
+    // a revocation which has passed may no longer be rescheduled
+    invariant!(
+        !ctx.accounts.scheduled_revocation_passed(now),
+        RevocationPassed
+    );
+
+    let stream = &mut ctx.accounts.stream;
+    stream.scheduled_revoke_ts = revoke_ts;
+
+    emit!(RevocationScheduleEvent {
+        stream: stream.key(),
+        revoker: ctx.accounts.revoker.key(),
+        revoke_ts,
+    });
+
+    Ok(())
+}
+
+/// Emitted on [crate::venko::schedule_revocation].
+#[event]
+pub struct RevocationScheduleEvent {
+    /// The [Stream].
+    #[index]
+    pub stream: Pubkey,
+    /// The [Stream::revoker].
+    pub revoker: Pubkey,
+    /// The time at which the [Stream] will be revoked.
+    pub revoke_ts: i64,
+}
+
+impl<'info> Validate<'info> for ScheduleRevocation<'info> {
+    fn validate(&self) -> ProgramResult {
+        invariant!(self.stream.revoker != Pubkey::default(), Irrevocable);
+        assert_keys_eq!(self.stream.revoker, self.revoker, NotRevoker);
+        self.stream.assert_active()?;
+        Ok(())
+    }
+}
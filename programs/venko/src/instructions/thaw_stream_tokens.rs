@@ -0,0 +1,73 @@
+//! Instruction handler for [crate::venko::thaw_stream_tokens].
+
+use crate::*;
+use anchor_spl::token::{self, Mint, TokenAccount};
+use vipers::{assert_keys_eq, invariant, Validate};
+
+/// Accounts for [venko::thaw_stream_tokens].
+#[derive(Accounts)]
+pub struct ThawStreamTokens<'info> {
+    /// [Stream] account.
+    pub stream: Box<Account<'info, VersionedStream>>,
+    /// [Mint] of the [Stream].
+    pub stream_mint: Account<'info, Mint>,
+    /// Underlying tokens of the [Stream].
+    pub underlying_tokens: Account<'info, TokenAccount>,
+
+    /// The frozen [TokenAccount] holding [Stream] tokens.
+    #[account(mut)]
+    pub stream_tokens: Account<'info, TokenAccount>,
+
+    /// SPL [token] program.
+    pub token_program: Program<'info, token::Token>,
+}
+
+pub fn handler(ctx: Context<ThawStreamTokens>) -> ProgramResult {
+    StreamTokensFreeze {
+        stream: &ctx.accounts.stream,
+        stream_mint: &ctx.accounts.stream_mint,
+        stream_tokens: &ctx.accounts.stream_tokens,
+        token_program: &ctx.accounts.token_program,
+    }
+    .thaw()?;
+
+    let stream = &ctx.accounts.stream;
+    emit!(StreamTokensThawEvent {
+        stream: stream.key(),
+        mint: stream.mint,
+        stream_tokens: ctx.accounts.stream_tokens.key(),
+        amount: ctx.accounts.stream_tokens.amount,
+    });
+
+    Ok(())
+}
+
+/// Emitted on [crate::venko::thaw_stream_tokens].
+#[event]
+pub struct StreamTokensThawEvent {
+    /// The [Stream].
+    #[index]
+    pub stream: Pubkey,
+    /// Mint of the [Stream] token.
+    #[index]
+    pub mint: Pubkey,
+    /// The thawed [TokenAccount].
+    pub stream_tokens: Pubkey,
+    /// Amount of [Stream] tokens in the account.
+    pub amount: u64,
+}
+
+impl<'info> Validate<'info> for ThawStreamTokens<'info> {
+    fn validate(&self) -> ProgramResult {
+        self.stream.assert_finished()?;
+
+        assert_keys_eq!(self.stream_mint, self.stream.mint);
+        assert_keys_eq!(self.underlying_tokens, self.stream.underlying_tokens);
+        // the tokens may be moved once they are no longer backed
+        invariant!(self.underlying_tokens.amount == 0, StreamNotDrained);
+
+        assert_keys_eq!(self.stream_tokens.mint, self.stream.mint);
+        invariant!(self.stream_tokens.is_frozen());
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+//! Instruction handler for [crate::venko::top_up].
+
+use crate::*;
+use anchor_spl::token::{self, Mint, TokenAccount, Transfer};
+use vipers::{assert_keys_eq, invariant, unwrap_int, Validate};
+
+/// Accounts for [venko::top_up].
+#[derive(Accounts)]
+pub struct TopUp<'info> {
+    /// [token::Mint] of the [Stream].
+    /// This account is `mut` because tokens are issued.
+    #[account(mut)]
+    pub stream_mint: Account<'info, Mint>,
+    /// [Stream] account.
+    #[account(mut)]
+    pub stream: Box<Account<'info, VersionedStream>>,
+
+    /// The [crate_token::CrateToken].
+    pub crate_token: Box<Account<'info, crate_token::CrateToken>>,
+
+    /// Underlying tokens of the [Stream].
+    #[account(mut)]
+    pub underlying_tokens: Account<'info, TokenAccount>,
+    /// Source of the additional underlying tokens.
+    #[account(mut)]
+    pub source_tokens: Account<'info, TokenAccount>,
+    /// Authority of the [Self::source_tokens]. Must be the [Stream::creator].
+    pub source_authority: Signer<'info>,
+
+    /// Destination of the new [Stream] tokens. Must be owned by the
+    /// [Stream::beneficiary] of a non-transferable [Stream].
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+
+    /// [crate_token] program.
+    pub crate_token_program: Program<'info, crate_token::program::CrateToken>,
+    /// SPL [token] program.
+    pub token_program: Program<'info, token::Token>,
+}
+
+impl<'info> TopUp<'info> {
+    fn top_up(&mut self, amount: u64) -> ProgramResult {
+        self.deposit_underlying(amount)?;
+        let remaining_amount = unwrap_int!(self.destination.amount.checked_add(amount));
+        StreamTokensFreeze {
+            stream: &self.stream,
+            stream_mint: &self.stream_mint,
+            stream_tokens: &self.destination,
+            token_program: &self.token_program,
+        }
+        .with_thawed(remaining_amount, || self.issue_tokens(amount))?;
+
+        unwrap_int!(self.stream.top_up(amount));
+
+        Ok(())
+    }
+
+    fn deposit_underlying(&self, amount: u64) -> ProgramResult {
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.source_tokens.to_account_info(),
+                    to: self.underlying_tokens.to_account_info(),
+                    authority: self.source_authority.to_account_info(),
+                },
+            ),
+            amount,
+        )
+    }
+
+    /// Issue the additional [Stream] tokens.
+    fn issue_tokens(&self, amount: u64) -> ProgramResult {
+        let signer_seeds: &[&[&[u8]]] = stream_seeds!(self.stream);
+        crate_token::cpi::issue(
+            CpiContext::new_with_signer(
+                self.crate_token_program.to_account_info(),
+                crate_token::cpi::accounts::Issue {
+                    crate_mint: self.stream_mint.to_account_info(),
+                    crate_token: self.crate_token.to_account_info(),
+
+                    // authorities
+                    issue_authority: self.stream.to_account_info(),
+
+                    mint_destination: self.destination.to_account_info(),
+                    author_fee_destination: self.destination.to_account_info(),
+                    protocol_fee_destination: self.destination.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<TopUp>, amount: u64) -> ProgramResult {
+    invariant!(amount > 0);
+    ctx.accounts.top_up(amount)?;
+
+    let stream = &ctx.accounts.stream;
+    emit!(TopUpEvent {
+        stream: stream.key(),
+        mint: stream.underlying_mint,
+        funder: ctx.accounts.source_authority.key(),
+        amount,
+        initial_amount: stream.initial_amount,
+    });
+
+    Ok(())
+}
+
+/// Emitted on [crate::venko::top_up].
+#[event]
+pub struct TopUpEvent {
+    /// The [Stream].
+    #[index]
+    pub stream: Pubkey,
+    /// Mint of the underlying token.
+    #[index]
+    pub mint: Pubkey,
+    /// Account that provided the additional tokens.
+    pub funder: Pubkey,
+
+    /// Tokens added to the [Stream].
+    pub amount: u64,
+    /// The new [Stream::initial_amount].
+    pub initial_amount: u64,
+}
+
+impl<'info> Validate<'info> for TopUp<'info> {
+    fn validate(&self) -> ProgramResult {
+        assert_keys_eq!(self.stream_mint, self.stream.mint);
+        self.stream.assert_active()?;
+        assert_keys_eq!(self.crate_token, self.stream.crate_token);
+        assert_keys_eq!(self.underlying_tokens, self.stream.underlying_tokens);
+
+        assert_keys_eq!(self.source_authority, self.stream.creator, NotCreator);
+        assert_keys_eq!(self.source_tokens.mint, self.stream.underlying_mint);
+
+        assert_keys_eq!(self.destination.mint, self.stream.mint);
+        if !self.stream.is_transferable() {
+            assert_keys_eq!(
+                self.destination.owner,
+                self.stream.beneficiary,
+                NotBeneficiary
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+//! Instruction handler for [crate::venko::transfer_revoker].
+
+use crate::*;
+use vipers::{assert_keys_eq, invariant, Validate};
+
+/// Accounts for [venko::transfer_revoker].
+#[derive(Accounts)]
+pub struct TransferRevoker<'info> {
+    /// [Stream] account.
+    #[account(mut)]
+    pub stream: Box<Account<'info, VersionedStream>>,
+    /// The [Stream::revoker].
+    pub revoker: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<TransferRevoker>, new_revoker: Pubkey) -> ProgramResult {
+    let stream = &mut ctx.accounts.stream;
+    stream.pending_revoker = new_revoker;
+
+    emit!(RevokerTransferEvent {
+        stream: stream.key(),
+        revoker: stream.revoker,
+        pending_revoker: new_revoker,
+    });
+
+    Ok(())
+}
+
+/// Emitted on [crate::venko::transfer_revoker].
+#[event]
+pub struct RevokerTransferEvent {
+    /// The [Stream].
+    #[index]
+    pub stream: Pubkey,
+    /// The current [Stream::revoker].
+    pub revoker: Pubkey,
+    /// The nominated [Stream::pending_revoker].
+    pub pending_revoker: Pubkey,
+}
+
+impl<'info> Validate<'info> for TransferRevoker<'info> {
+    fn validate(&self) -> ProgramResult {
+        invariant!(self.stream.revoker != Pubkey::default(), Irrevocable);
+        assert_keys_eq!(self.stream.revoker, self.revoker, NotRevoker);
+        self.stream.assert_not_finished()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+//! Instruction handler for [crate::venko::update_stream_metadata].
+
+use crate::*;
+use vipers::{invariant, Validate};
+
+/// Accounts for [venko::update_stream_metadata].
+#[derive(Accounts)]
+pub struct UpdateStreamMetadata<'info> {
+    /// [Stream] account.
+    #[account(mut)]
+    pub stream: Box<Account<'info, VersionedStream>>,
+    /// The [Stream::creator] or [Stream::revoker].
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateStreamMetadata>, metadata: StreamMetadata) -> ProgramResult {
+    let stream = &mut ctx.accounts.stream;
+    stream.metadata = metadata;
+
+    emit!(StreamMetadataUpdateEvent {
+        stream: stream.key(),
+        authority: ctx.accounts.authority.key(),
+        metadata,
+    });
+
+    Ok(())
+}
+
+/// Emitted on [crate::venko::update_stream_metadata].
+#[event]
+pub struct StreamMetadataUpdateEvent {
+    /// The [Stream].
+    #[index]
+    pub stream: Pubkey,
+    /// The account which updated the metadata.
+    pub authority: Pubkey,
+    /// The new [Stream::metadata].
+    pub metadata: StreamMetadata,
+}
+
+impl<'info> Validate<'info> for UpdateStreamMetadata<'info> {
+    fn validate(&self) -> ProgramResult {
+        let authority = self.authority.key();
+        let is_revoker =
+            self.stream.revoker != Pubkey::default() && self.stream.revoker == authority;
+        invariant!(
+            self.stream.creator == authority || is_revoker,
+            NotCreatorOrRevoker
+        );
+        self.stream.assert_not_finished()?;
+        Ok(())
+    }
+}
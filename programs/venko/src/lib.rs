@@ -47,11 +47,16 @@ mod macros;
 use anchor_lang::prelude::*;
 use vipers::Validate;
 
+mod freeze;
 mod instructions;
+pub mod merkle_proof;
 mod state;
+mod withdraw;
 
+pub(crate) use freeze::*;
 pub use instructions::*;
 pub use state::*;
+pub(crate) use withdraw::*;
 
 declare_id!("AnatoLyYrd5iaAe36Lvq2oS4nuVDnRAb3KBVCARt4XiZ");
 
@@ -60,34 +65,186 @@ declare_id!("AnatoLyYrd5iaAe36Lvq2oS4nuVDnRAb3KBVCARt4XiZ");
 pub mod venko {
     use super::*;
 
-    /// Creates a new [Stream].
+    /// Creates a new [Stream], depositing `amount` underlying tokens from the
+    /// [CreateStream::source_tokens] into the Crate.
+    ///
+    /// The underlying token account of the Crate is created if it does not
+    /// exist. Any tokens it already holds are also streamed.
+    ///
+    /// The [Stream] is added to the [StreamIndex] of its [Stream::creator] and
+    /// to the [StreamIndex] of the owner of the [CreateStream::destination].
+    ///
+    /// If `transferable` is false, the owner of the
+    /// [CreateStream::destination] is recorded as the [Stream::beneficiary],
+    /// and only they may redeem the [Stream] tokens. The [Stream] must then
+    /// be the freeze authority of the [CreateStream::stream_mint], and the
+    /// tokens are kept frozen in the [CreateStream::destination]. To enable
+    /// `auto_redeem`, the beneficiary must approve the [Stream] as a delegate
+    /// of the destination before the tokens are frozen.
+    ///
+    /// If `cancel_until_ts` is non-zero, the [Stream::creator] may cancel the
+    /// [Stream] via [venko::cancel_stream] until that time, and no tokens may
+    /// be redeemed before then. It may not be after `end_ts`.
+    ///
+    /// If `acceptance_deadline` is non-zero, the [Stream] is created
+    /// [StreamStatus::Pending], and its schedule and `cancel_until_ts` are
+    /// relative to the time at which the recipient accepts it via
+    /// [venko::accept_stream].
+    ///
+    /// If `withdraw_fee_bps` is non-zero, the Crate charges that fee on every
+    /// withdrawal of underlying tokens, paying it to the
+    /// [CreateStream::author_fee_to] less the Crate protocol fee. The fees
+    /// may not be changed afterwards. Refunds of underlying tokens to the
+    /// creator or revoker are not charged fees.
     #[access_control(ctx.accounts.validate())]
+    #[allow(clippy::too_many_arguments)]
     pub fn create_stream(
         ctx: Context<CreateStream>,
         stream_bump: u8,
         crate_bump: u8,
+        creator_index_bump: u8,
+        creator_entry_bump: u8,
+        recipient_index_bump: u8,
+        recipient_entry_bump: u8,
         start_ts: i64,
         cliff_ts: i64,
+        cliff_amount: u64,
         end_ts: i64,
+        period: i64,
+        breakpoints: Vec<Breakpoint>,
         revoker: Pubkey,
+        auto_redeem: bool,
+        transferable: bool,
+        cancel_until_ts: i64,
+        acceptance_deadline: i64,
+        withdraw_fee_bps: u16,
+        amount: u64,
+        metadata: StreamMetadata,
     ) -> ProgramResult {
         instructions::create_stream::handler(
             ctx,
             stream_bump,
             crate_bump,
+            creator_index_bump,
+            creator_entry_bump,
+            recipient_index_bump,
+            recipient_entry_bump,
+            start_ts,
+            cliff_ts,
+            cliff_amount,
+            end_ts,
+            period,
+            breakpoints,
+            revoker,
+            auto_redeem,
+            transferable,
+            cancel_until_ts,
+            acceptance_deadline,
+            withdraw_fee_bps,
+            amount,
+            metadata,
+        )
+    }
+
+    /// Creates a [Stream] for each of several recipients, sharing a single
+    /// release schedule and funded from a single source of underlying tokens.
+    ///
+    /// The accounts of each [StreamLeg] are passed as remaining accounts. See
+    /// [CreateStreamBatch] for their order. If any [Stream] cannot be created,
+    /// the entire batch fails.
+    ///
+    /// Since the amounts differ per recipient, [Stream::breakpoints] are not
+    /// supported, and the cliff amount is set per [StreamLeg]. The
+    /// [Stream::metadata] may be set afterwards via
+    /// [venko::update_stream_metadata].
+    ///
+    /// Batches only support a subset of the options of [venko::create_stream].
+    /// Every [Stream] in a batch is transferable, may not be cancelled, is
+    /// active upon creation and charges no withdraw fees; its Crate has no fee
+    /// setter, so no fees may be set later either. The underlying token
+    /// account of each Crate is not created, and must already exist. Use
+    /// [venko::create_stream] for [Stream]s which need any other option.
+    #[access_control(ctx.accounts.validate())]
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stream_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateStreamBatch<'info>>,
+        creator_index_bump: u8,
+        start_ts: i64,
+        cliff_ts: i64,
+        end_ts: i64,
+        period: i64,
+        revoker: Pubkey,
+        auto_redeem: bool,
+        legs: Vec<StreamLeg>,
+    ) -> ProgramResult {
+        instructions::create_stream_batch::handler(
+            ctx,
+            creator_index_bump,
             start_ts,
             cliff_ts,
             end_ts,
+            period,
             revoker,
+            auto_redeem,
+            legs,
         )
     }
 
+    /// Creates a [HolderClaim], which tracks the tokens redeemed by an owner
+    /// of [Stream] tokens. Required for [venko::redeem].
+    pub fn create_holder_claim(ctx: Context<CreateHolderClaim>, bump: u8) -> ProgramResult {
+        instructions::create_holder_claim::handler(ctx, bump)
+    }
+
     /// Redeems [Stream] tokens for their underlying.
+    ///
+    /// Each owner of [Stream] tokens may only redeem their pro-rata share of
+    /// the released tokens. The tokens may be redeemed by either the owner or
+    /// an approved delegate of the [Stream] token account.
+    ///
+    /// The share is tracked by the [HolderClaim] of the owner, so it does not
+    /// follow tokens moved to another owner: the new owner may redeem the
+    /// released share of those tokens again, which reduces the tokens left
+    /// for the other holders until more are released. The tokens of
+    /// non-transferable [Stream]s are frozen and cannot be moved.
     #[access_control(ctx.accounts.validate())]
     pub fn redeem(ctx: Context<Redeem>, amount: u64) -> ProgramResult {
         instructions::redeem::handler(ctx, amount)
     }
 
+    /// Redeems as many [Stream] tokens as possible for their underlying,
+    /// i.e. the lesser of the released amount and the user's [Stream] tokens.
+    #[access_control(ctx.accounts.validate())]
+    pub fn redeem_max(ctx: Context<Redeem>) -> ProgramResult {
+        instructions::redeem_max::handler(ctx)
+    }
+
+    /// Redeems all released tokens on behalf of a holder, sending them to the
+    /// holder's associated token account.
+    ///
+    /// The [Stream] must have [Stream::auto_redeem] enabled, and the holder must
+    /// have approved the [Stream] as a delegate of their [Stream] tokens.
+    /// Anyone may call this instruction.
+    #[access_control(ctx.accounts.validate())]
+    pub fn crank_redeem(ctx: Context<CrankRedeem>) -> ProgramResult {
+        instructions::crank_redeem::handler(ctx)
+    }
+
+    /// Adds more underlying tokens to a [Stream], issuing new [Stream] tokens
+    /// to a destination.
+    ///
+    /// The additional tokens are released on the same schedule as the
+    /// original deposit, so the portion of the schedule that has already
+    /// elapsed is immediately available for redemption.
+    ///
+    /// Only the [Stream::creator] may call this instruction. The new tokens
+    /// of a non-transferable [Stream] may only be issued to its
+    /// [Stream::beneficiary].
+    #[access_control(ctx.accounts.validate())]
+    pub fn top_up(ctx: Context<TopUp>, amount: u64) -> ProgramResult {
+        instructions::top_up::handler(ctx, amount)
+    }
+
     /// Revokes all underlying [Stream] tokens, invalidating them
     /// and sending all of the [Stream::underlying_tokens] to an address.
     ///
@@ -100,6 +257,224 @@ pub mod venko {
     pub fn revoke(ctx: Context<Revoke>) -> ProgramResult {
         instructions::revoke::handler(ctx)
     }
+
+    /// Revokes the unvested underlying [Stream] tokens, sending them to an
+    /// address. Tokens vested as of the time of revocation remain redeemable
+    /// by [Stream] token holders.
+    ///
+    /// Only the [Stream::revoker] may call this instruction.
+    #[access_control(ctx.accounts.validate())]
+    pub fn revoke_unvested(ctx: Context<Revoke>) -> ProgramResult {
+        instructions::revoke_unvested::handler(ctx)
+    }
+
+    /// Schedules the revocation of a [Stream] at a future time. No more
+    /// tokens are released after that time.
+    ///
+    /// Only the [Stream::revoker] may call this instruction.
+    #[access_control(ctx.accounts.validate())]
+    pub fn schedule_revocation(ctx: Context<ScheduleRevocation>, revoke_ts: i64) -> ProgramResult {
+        instructions::schedule_revocation::handler(ctx, revoke_ts)
+    }
+
+    /// Cancels a scheduled revocation which has not yet taken effect.
+    ///
+    /// Only the [Stream::revoker] may call this instruction.
+    #[access_control(ctx.accounts.validate())]
+    pub fn cancel_revocation(ctx: Context<ScheduleRevocation>) -> ProgramResult {
+        instructions::cancel_revocation::handler(ctx)
+    }
+
+    /// Executes a scheduled revocation once it has passed, sending the
+    /// unvested tokens to the [Stream::revoker].
+    ///
+    /// Anyone may call this instruction.
+    #[access_control(ctx.accounts.validate())]
+    pub fn execute_revocation(ctx: Context<ExecuteRevocation>) -> ProgramResult {
+        instructions::execute_revocation::handler(ctx)
+    }
+
+    /// Nominates a new [Stream::revoker], who must accept the role via
+    /// [venko::accept_revoker]. The roles of a finished [Stream] are fixed.
+    ///
+    /// Only the [Stream::revoker] may call this instruction.
+    #[access_control(ctx.accounts.validate())]
+    pub fn transfer_revoker(ctx: Context<TransferRevoker>, new_revoker: Pubkey) -> ProgramResult {
+        instructions::transfer_revoker::handler(ctx, new_revoker)
+    }
+
+    /// Accepts the [Stream::revoker] role.
+    ///
+    /// Only the [Stream::pending_revoker] may call this instruction.
+    #[access_control(ctx.accounts.validate())]
+    pub fn accept_revoker(ctx: Context<AcceptRevoker>) -> ProgramResult {
+        instructions::accept_revoker::handler(ctx)
+    }
+
+    /// Permanently renounces the [Stream::revoker] role, making the [Stream]
+    /// irrevocable. Any scheduled revocation which has not yet taken effect
+    /// is cancelled.
+    ///
+    /// Only the [Stream::revoker] may call this instruction.
+    #[access_control(ctx.accounts.validate())]
+    pub fn renounce_revoker(ctx: Context<TransferRevoker>) -> ProgramResult {
+        instructions::renounce_revoker::handler(ctx)
+    }
+
+    /// Creates a [VestingDistributor], which releases a single pool of
+    /// underlying tokens to many claimants on a shared schedule.
+    ///
+    /// The allocations of the claimants are committed to by the `root` of a
+    /// Merkle tree of (claimant, allocation) entries. The underlying tokens
+    /// must be deposited into the [VestingDistributor::underlying_tokens].
+    #[access_control(ctx.accounts.validate())]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_vesting_distributor(
+        ctx: Context<NewVestingDistributor>,
+        bump: u8,
+        root: [u8; 32],
+        max_total_claim: u64,
+        start_ts: i64,
+        cliff_ts: i64,
+        end_ts: i64,
+        period: i64,
+    ) -> ProgramResult {
+        instructions::new_vesting_distributor::handler(
+            ctx,
+            bump,
+            root,
+            max_total_claim,
+            start_ts,
+            cliff_ts,
+            end_ts,
+            period,
+        )
+    }
+
+    /// Creates a [ClaimStatus] for a claimant of a [VestingDistributor] by
+    /// proving their allocation. Required for [venko::claim_vested].
+    ///
+    /// Anyone may call this instruction, until the entire
+    /// [VestingDistributor::max_total_claim] has been redeemed.
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_claim_status(
+        ctx: Context<CreateClaimStatus>,
+        bump: u8,
+        allocation: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> ProgramResult {
+        instructions::create_claim_status::handler(ctx, bump, allocation, proof)
+    }
+
+    /// Claims all tokens of a claimant's allocation which have been released
+    /// but not yet redeemed from a [VestingDistributor].
+    #[access_control(ctx.accounts.validate())]
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> ProgramResult {
+        instructions::claim_vested::handler(ctx)
+    }
+
+    /// Updates the [Stream::metadata], unless the [Stream] has finished.
+    ///
+    /// Only the [Stream::creator] or the [Stream::revoker] may call this
+    /// instruction.
+    #[access_control(ctx.accounts.validate())]
+    pub fn update_stream_metadata(
+        ctx: Context<UpdateStreamMetadata>,
+        metadata: StreamMetadata,
+    ) -> ProgramResult {
+        instructions::update_stream_metadata::handler(ctx, metadata)
+    }
+
+    /// Pauses a [Stream], stopping the release of tokens until it is resumed.
+    /// A [Stream] may not be paused once its scheduled revocation has passed.
+    ///
+    /// Only the [Stream::revoker] may call this instruction.
+    #[access_control(ctx.accounts.validate())]
+    pub fn pause(ctx: Context<Pause>) -> ProgramResult {
+        instructions::pause::handler(ctx)
+    }
+
+    /// Resumes a paused [Stream]. The time spent paused is added to the
+    /// schedule, so all tokens are still released in full. Only the time
+    /// spent paused before a scheduled revocation is added.
+    ///
+    /// Only the [Stream::revoker] may call this instruction.
+    #[access_control(ctx.accounts.validate())]
+    pub fn resume(ctx: Context<Pause>) -> ProgramResult {
+        instructions::resume::handler(ctx)
+    }
+
+    /// Cancels a [Stream] before its [Stream::cancel_until_ts], or a
+    /// [StreamStatus::Pending] [Stream] after its [Stream::acceptance_deadline],
+    /// refunding all of the [Stream::underlying_tokens] to an address.
+    ///
+    /// Only the [Stream::creator] may call this instruction.
+    #[access_control(ctx.accounts.validate())]
+    pub fn cancel_stream(ctx: Context<CancelStream>) -> ProgramResult {
+        instructions::cancel_stream::handler(ctx)
+    }
+
+    /// Accepts a [StreamStatus::Pending] [Stream], starting its schedule at
+    /// the current time.
+    ///
+    /// Only the holder of the entire supply of [Stream] tokens may call this
+    /// instruction, before the [Stream::acceptance_deadline].
+    #[access_control(ctx.accounts.validate())]
+    pub fn accept_stream(ctx: Context<AcceptStream>) -> ProgramResult {
+        instructions::accept_stream::handler(ctx)
+    }
+
+    /// Rejects an [StreamStatus::Active] or [StreamStatus::Pending] [Stream],
+    /// burning all of its [Stream] tokens and returning all of the
+    /// [Stream::underlying_tokens] to the [Stream::creator].
+    ///
+    /// Only the holder of the entire supply of [Stream] tokens may call this
+    /// instruction. The [Stream] may then be closed via
+    /// [venko::close_stream].
+    #[access_control(ctx.accounts.validate())]
+    pub fn reject_stream(ctx: Context<RejectStream>) -> ProgramResult {
+        instructions::reject_stream::handler(ctx)
+    }
+
+    /// Migrates a version 0 [Stream] account from the [StreamV0] layout to
+    /// the current layout. The payer funds the rent of the additional space.
+    ///
+    /// Other instructions load both layouts, but fail with
+    /// [ErrorCode::StreamMigrationRequired] on changes which the version 0
+    /// layout cannot represent, so the SDK prepends this instruction to any
+    /// transaction using a version 0 [Stream].
+    ///
+    /// Anyone may call this instruction.
+    #[access_control(ctx.accounts.validate())]
+    pub fn migrate_stream(ctx: Context<MigrateStream>) -> ProgramResult {
+        instructions::migrate_stream::handler(ctx)
+    }
+
+    /// Closes a finished [Stream] which has no [Stream] tokens and no
+    /// underlying tokens left, sending its rent to a recipient.
+    ///
+    /// The [Stream::underlying_tokens] account is owned by the
+    /// [crate_token::CrateToken], which provides no way to close it, so it
+    /// remains open.
+    ///
+    /// Only the [Stream::creator], who paid the rent, may call this
+    /// instruction. Version 0 [Stream]s did not record their creator, so their
+    /// [Stream::revoker] acts as creator. Irrevocable version 0 [Stream]s may
+    /// not be closed.
+    #[access_control(ctx.accounts.validate())]
+    pub fn close_stream(ctx: Context<CloseStream>) -> ProgramResult {
+        instructions::close_stream::handler(ctx)
+    }
+
+    /// Thaws the [Stream] tokens of a non-transferable [Stream] once it is
+    /// finished and holds no underlying tokens, so that its holder may burn
+    /// them and the [Stream] may be closed via [venko::close_stream].
+    ///
+    /// Anyone may call this instruction.
+    #[access_control(ctx.accounts.validate())]
+    pub fn thaw_stream_tokens(ctx: Context<ThawStreamTokens>) -> ProgramResult {
+        instructions::thaw_stream_tokens::handler(ctx)
+    }
 }
 
 /// Errors.
@@ -115,4 +490,84 @@ pub enum ErrorCode {
     Irrevocable,
     #[msg("Must be revoker to perform this operation.")]
     NotRevoker,
+    #[msg("Release period must be non-negative and no longer than the stream.")]
+    InvalidPeriod,
+    #[msg("Cliff amount must not exceed the amount of tokens in the stream.")]
+    InvalidCliffAmount,
+    #[msg("Breakpoints must be monotonic and end with the full stream amount.")]
+    InvalidBreakpoints,
+    #[msg("Too many breakpoints in the release schedule.")]
+    TooManyBreakpoints,
+    #[msg("Stream has already been revoked.")]
+    AlreadyRevoked,
+    #[msg("Revocation must not be scheduled in the past.")]
+    InvalidRevocationTime,
+    #[msg("No revocation is scheduled.")]
+    RevocationNotScheduled,
+    #[msg("Scheduled revocation has not yet taken effect.")]
+    RevocationPending,
+    #[msg("Scheduled revocation has already taken effect.")]
+    RevocationPassed,
+    #[msg("Must be pending revoker to perform this operation.")]
+    NotPendingRevoker,
+    #[msg("Stream is paused.")]
+    Paused,
+    #[msg("Stream is not paused.")]
+    NotPaused,
+    #[msg("Stream still has tokens outstanding.")]
+    StreamNotDrained,
+    #[msg("Must be the owner or delegate of the stream tokens.")]
+    InvalidRedeemAuthority,
+    #[msg("Auto-redeem is not enabled for this stream.")]
+    AutoRedeemDisabled,
+    #[msg("Stream must be a delegate of the stream tokens.")]
+    StreamNotDelegate,
+    #[msg("Batch legs do not match the provided accounts.")]
+    InvalidBatch,
+    #[msg("Invalid Merkle proof.")]
+    InvalidProof,
+    #[msg("Exceeded the maximum total claim of the distributor.")]
+    ExceededMaxClaim,
+    #[msg("Must be creator or revoker to perform this operation.")]
+    NotCreatorOrRevoker,
+    #[msg("Stream has been completed.")]
+    StreamCompleted,
+    #[msg("Stream has already been migrated to the current version.")]
+    StreamAlreadyMigrated,
+    #[msg("Only the beneficiary may hold or redeem non-transferable stream tokens.")]
+    NotBeneficiary,
+    #[msg("Stream has been cancelled.")]
+    StreamCancelled,
+    #[msg("Must be creator to perform this operation.")]
+    NotCreator,
+    #[msg("Stream may still be cancelled by its creator.")]
+    CancellationWindowOpen,
+    #[msg("Stream may no longer be cancelled.")]
+    CancellationWindowClosed,
+    #[msg("Stream has been rejected.")]
+    StreamRejected,
+    #[msg("Must hold the entire supply of stream tokens.")]
+    NotSoleHolder,
+    #[msg("Stream has not been accepted.")]
+    StreamPending,
+    #[msg("Acceptance deadline must be in the future.")]
+    InvalidAcceptanceDeadline,
+    #[msg("Acceptance deadline has passed.")]
+    AcceptanceDeadlinePassed,
+    #[msg("Stream may still be accepted by its recipient.")]
+    AcceptancePending,
+    #[msg("Stream is not pending acceptance.")]
+    StreamNotPending,
+    #[msg("Withdraw fee must not exceed 1,000 basis points.")]
+    InvalidWithdrawFee,
+    #[msg("Author fee destination must be owned by the author fee recipient.")]
+    InvalidFeeDestination,
+    #[msg("All tokens of the vesting distributor have been redeemed.")]
+    DistributorExhausted,
+    #[msg("Freeze authority must be the Stream if and only if it is non-transferable.")]
+    InvalidFreezeAuthority,
+    #[msg("Cancellation window must not extend past the end of the stream.")]
+    InvalidCancellationWindow,
+    #[msg("Stream must be migrated to the current version for this change.")]
+    StreamMigrationRequired,
 }
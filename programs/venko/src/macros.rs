@@ -11,3 +11,15 @@ macro_rules! stream_seeds {
         ]]
     };
 }
+
+/// Generates the signer seeds for a [crate::VestingDistributor].
+#[macro_export]
+macro_rules! distributor_seeds {
+    ($distributor: expr) => {
+        &[&[
+            b"VestingDistributor" as &[u8],
+            &$distributor.base.to_bytes(),
+            &[$distributor.bump],
+        ]]
+    };
+}
@@ -0,0 +1,43 @@
+//! Verification of Merkle proofs of [crate::VestingDistributor] allocations.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hashv;
+
+/// Computes the leaf of a (claimant, allocation) entry.
+pub fn leaf(claimant: &Pubkey, allocation: u64) -> [u8; 32] {
+    hashv(&[&claimant.to_bytes(), &allocation.to_le_bytes()]).to_bytes()
+}
+
+/// Returns true if the `proof` shows that the `leaf` is part of the tree with
+/// the given `root`. Each pair of nodes is sorted before being hashed.
+pub fn verify(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+    computed == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_two_leaves() {
+        let alice = leaf(&Pubkey::new_unique(), 1_000);
+        let bob = leaf(&Pubkey::new_unique(), 2_000);
+        let root = if alice <= bob {
+            hashv(&[&alice, &bob]).to_bytes()
+        } else {
+            hashv(&[&bob, &alice]).to_bytes()
+        };
+
+        assert!(verify(&[bob], root, alice));
+        assert!(verify(&[alice], root, bob));
+        assert!(!verify(&[alice], root, alice));
+        assert!(!verify(&[], root, alice));
+    }
+}
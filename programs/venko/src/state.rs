@@ -1,23 +1,37 @@
 //! Struct definitions for accounts that hold state.
 
 use crate::*;
+use anchor_lang::Discriminator;
 use num_traits::ToPrimitive;
+use std::io::Write;
+use std::ops::{Deref, DerefMut};
+
+/// The maximum number of [Breakpoint]s in a [Stream]'s release schedule.
+pub const MAX_BREAKPOINTS: usize = 12;
+
+/// The maximum fee charged by the Crate on withdrawals, in basis points.
+pub const MAX_WITHDRAW_FEE_BPS: u16 = 1_000;
 
 /// A stream of tokens being sent, with a cliff and an optional [Self::revoker].
 ///
 /// When a [Stream] is created, there is one token created for every underlying
 /// token backing the [Stream].
 #[account]
-#[derive(Copy, Debug, Default)]
+#[derive(Copy, Debug, Default, PartialEq)]
 pub struct Stream {
     /// The mint of the [Stream] token.
     pub mint: Pubkey,
     /// Bump seed.
     pub bump: u8,
+    /// The account which funded the [Stream].
+    pub creator: Pubkey,
 
     /// An optional account which may invalidate this stream and receive all of the underlying tokens.
     /// If the default [Pubkey] is used, it is considered to be irrevocable.
     pub revoker: Pubkey,
+    /// The account nominated to become the next [Self::revoker], which must
+    /// accept the role. The default [Pubkey] if there is no nominee.
+    pub pending_revoker: Pubkey,
     /// The Crate Token.
     pub crate_token: Pubkey,
     /// The mint of the SPL token locked up.
@@ -31,6 +45,251 @@ pub struct Stream {
     /// The total amount of tokens that have been redeemed from the [Stream].
     pub redeemed_amount: u64,
 
+    /// The time at which the [Stream] begins.
+    pub start_ts: i64,
+    /// The time at which the [Stream] starts paying out its tokens.
+    pub cliff_ts: i64,
+    /// The amount of tokens released in full at [Self::cliff_ts].
+    /// If non-zero, the remaining tokens are released linearly from
+    /// [Self::cliff_ts] to [Self::end_ts] rather than from [Self::start_ts].
+    pub cliff_amount: u64,
+    /// The time at which all tokens are released.
+    pub end_ts: i64,
+    /// The lifecycle status of the [Stream].
+    pub status: StreamStatus,
+    /// The time at which the [Stream] was revoked, after which no more tokens
+    /// are released. Zero if the [Stream] has not been revoked.
+    pub revoked_at: i64,
+    /// The time at which the [Self::revoker] has scheduled the [Stream] to be
+    /// revoked. No more tokens are released after this time.
+    /// Zero if no revocation is scheduled.
+    pub scheduled_revoke_ts: i64,
+    /// The length of each release period, in seconds. Tokens are released
+    /// in discrete steps at the end of every period since [Self::start_ts],
+    /// or since [Self::cliff_ts] if [Self::cliff_amount] is non-zero.
+    /// If zero, tokens are released continuously.
+    pub period: i64,
+    /// The time at which the [Stream] was paused. Zero if not paused.
+    pub paused_at: i64,
+    /// The total number of seconds the [Stream] has spent paused, excluding
+    /// the current pause. The schedule is shifted forward by this amount.
+    pub paused_duration: i64,
+    /// If true, anyone may redeem released tokens on behalf of a holder who
+    /// has approved the [Stream] as a delegate of their [Stream] tokens.
+    /// The underlying tokens may only be sent to the holder.
+    pub auto_redeem: bool,
+
+    /// The number of [Self::breakpoints] in use.
+    pub num_breakpoints: u8,
+    /// An optional piecewise-linear release schedule. If any breakpoints are
+    /// set, they replace the linear release between [Self::start_ts] and
+    /// [Self::end_ts].
+    pub breakpoints: [Breakpoint; 12],
+
+    /// Descriptive information about the [Stream].
+    pub metadata: StreamMetadata,
+
+    /// The version of the layout of the [Stream] account. See
+    /// [Stream::VERSION].
+    pub version: u8,
+    /// The only account which may redeem the [Stream] tokens, or the default
+    /// [Pubkey] if the [Stream] tokens are transferable.
+    pub beneficiary: Pubkey,
+    /// The time until which the [Self::creator] may cancel the [Stream] for a
+    /// full refund. Tokens may not be redeemed before this time, which is no
+    /// later than [Self::end_ts].
+    /// Zero if the [Stream] may not be cancelled.
+    pub cancel_until_ts: i64,
+    /// The time until which the recipient may accept a
+    /// [StreamStatus::Pending] [Stream]. Zero if the [Stream] was not created
+    /// pending acceptance.
+    pub acceptance_deadline: i64,
+    /// The recipient of the author fees charged by the Crate on withdrawals,
+    /// or the default [Pubkey] if the [Stream] charges no fees.
+    pub author_fee_to: Pubkey,
+    /// Reserved for fields added in future versions. Must be zero.
+    pub reserved: [u64; 6],
+}
+
+/// Tracks the amount of tokens redeemed from a [Stream] by an owner of
+/// [Stream] tokens.
+///
+/// Claims are keyed by owner rather than by token account, so that moving
+/// [Stream] tokens to a new account of the same owner does not reset the
+/// amount they have redeemed.
+#[account]
+#[derive(Copy, Debug, Default)]
+pub struct HolderClaim {
+    /// The [Stream].
+    pub stream: Pubkey,
+    /// The owner of the [Stream] token accounts.
+    pub owner: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+
+    /// The total amount of tokens that have been redeemed by [Self::owner].
+    pub redeemed_amount: u64,
+}
+
+/// Counts the [Stream]s indexed under a key, e.g. the [Stream]s created by an
+/// account. Each [Stream] in the index is pointed at by a [StreamIndexEntry].
+#[account]
+#[derive(Copy, Debug, Default)]
+pub struct StreamIndex {
+    /// The key the [Stream]s are indexed under.
+    pub key: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+
+    /// The number of [StreamIndexEntry]s in the index.
+    pub count: u64,
+}
+
+/// Points at the [Stream] at a position of a [StreamIndex].
+#[account]
+#[derive(Copy, Debug, Default)]
+pub struct StreamIndexEntry {
+    /// The [StreamIndex].
+    pub index: Pubkey,
+    /// The position of this entry in the [StreamIndex].
+    pub position: u64,
+    /// Bump seed.
+    pub bump: u8,
+
+    /// The [Stream].
+    pub stream: Pubkey,
+}
+
+/// A pool of underlying tokens released to many claimants on a shared
+/// schedule. The allocation of each claimant is committed to by a Merkle root
+/// of (claimant, allocation) entries.
+#[account]
+#[derive(Copy, Debug, Default)]
+pub struct VestingDistributor {
+    /// Base key used to derive the [VestingDistributor] address.
+    pub base: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+
+    /// Root of the Merkle tree of (claimant, allocation) entries.
+    pub root: [u8; 32],
+    /// The mint of the SPL token distributed.
+    pub underlying_mint: Pubkey,
+    /// Token account holding the underlying tokens.
+    pub underlying_tokens: Pubkey,
+
+    /// The sum of all allocations in the Merkle tree.
+    pub max_total_claim: u64,
+    /// The total amount of tokens that have been redeemed by all claimants.
+    pub total_redeemed: u64,
+
+    /// The time at which the release begins.
+    pub start_ts: i64,
+    /// The time at which claimants may start redeeming their tokens.
+    pub cliff_ts: i64,
+    /// The time at which all tokens are released.
+    pub end_ts: i64,
+    /// The length of each release period, in seconds. If zero, tokens are
+    /// released continuously.
+    pub period: i64,
+}
+
+/// Tracks the allocation of a claimant of a [VestingDistributor] and the
+/// amount they have redeemed.
+#[account]
+#[derive(Copy, Debug, Default)]
+pub struct ClaimStatus {
+    /// The [VestingDistributor].
+    pub distributor: Pubkey,
+    /// The claimant.
+    pub claimant: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+
+    /// The total amount of tokens allocated to the [Self::claimant].
+    pub allocation: u64,
+    /// The total amount of tokens that have been redeemed by the
+    /// [Self::claimant].
+    pub redeemed_amount: u64,
+}
+
+/// The lifecycle status of a [Stream].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StreamStatus {
+    /// Tokens are being released according to the schedule.
+    #[default]
+    Active,
+    /// The [Stream] has been revoked, so no more tokens are released. Tokens
+    /// released before [Stream::revoked_at] may still be redeemed.
+    Revoked,
+    /// All of the tokens of the [Stream] have been redeemed.
+    Completed,
+    /// The [Stream] was cancelled by its [Stream::creator] before
+    /// [Stream::cancel_until_ts], or after its [Stream::acceptance_deadline]
+    /// passed, refunding all of its tokens.
+    Cancelled,
+    /// The [Stream] was rejected by the holder of all of its [Stream] tokens,
+    /// returning all of its tokens to the [Stream::creator].
+    Rejected,
+    /// The [Stream] is waiting to be accepted by its recipient. Its schedule
+    /// is relative to the time of acceptance.
+    Pending,
+}
+
+/// Descriptive information about a [Stream], e.g. for reconciling it with
+/// the contract or invoice it pays out.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StreamMetadata {
+    /// A short name, UTF-8 encoded and padded with zeros.
+    pub name: [u8; 32],
+    /// A memo, UTF-8 encoded and padded with zeros.
+    pub memo: [u8; 64],
+    /// A hash of an external reference, e.g. a contract or invoice.
+    pub external_ref: [u8; 32],
+}
+
+impl Default for StreamMetadata {
+    fn default() -> Self {
+        Self {
+            name: [0; 32],
+            memo: [0; 64],
+            external_ref: [0; 32],
+        }
+    }
+}
+
+/// A point on a piecewise-linear release schedule.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Breakpoint {
+    /// The time of this point.
+    pub ts: i64,
+    /// The total amount of tokens released as of [Self::ts].
+    pub amount: u64,
+}
+
+/// The layout of a version 0 [Stream] account, created before
+/// [Stream::version] was added.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StreamV0 {
+    /// The mint of the [Stream] token.
+    pub mint: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+
+    /// The [Stream::revoker].
+    pub revoker: Pubkey,
+    /// The Crate Token.
+    pub crate_token: Pubkey,
+    /// The mint of the SPL token locked up.
+    pub underlying_mint: Pubkey,
+    /// Token account holding the underlying tokens.
+    pub underlying_tokens: Pubkey,
+
+    /// The starting balance of this release account.
+    pub initial_amount: u64,
+    /// The total amount of tokens that have been redeemed from the [Stream].
+    pub redeemed_amount: u64,
+
     /// The time at which the [Stream] begins.
     pub start_ts: i64,
     /// The time at which the [Stream] starts paying out its tokens.
@@ -39,13 +298,293 @@ pub struct Stream {
     pub end_ts: i64,
 }
 
+impl StreamV0 {
+    /// The size of a version 0 [Stream] account, including the discriminator.
+    pub const LEN: usize = 209;
+
+    /// Maps a version 0 account to the current [Stream] layout.
+    ///
+    /// Version 0 revocations overwrote the schedule to look fully redeemed,
+    /// so revoked and completed [Stream]s cannot be told apart and are both
+    /// migrated as [StreamStatus::Completed]. The creator of a version 0
+    /// [Stream] was not recorded, so its [Stream::revoker] acts as its
+    /// [Stream::creator]. An irrevocable version 0 [Stream] has no creator.
+    pub fn migrate(&self) -> Stream {
+        let status = if self.initial_amount > 0 && self.redeemed_amount >= self.initial_amount {
+            StreamStatus::Completed
+        } else {
+            StreamStatus::Active
+        };
+        Stream {
+            mint: self.mint,
+            bump: self.bump,
+            revoker: self.revoker,
+            crate_token: self.crate_token,
+            underlying_mint: self.underlying_mint,
+            underlying_tokens: self.underlying_tokens,
+            initial_amount: self.initial_amount,
+            redeemed_amount: self.redeemed_amount,
+            start_ts: self.start_ts,
+            cliff_ts: self.cliff_ts,
+            end_ts: self.end_ts,
+            status,
+            creator: self.revoker,
+            version: Stream::VERSION,
+            ..Stream::default()
+        }
+    }
+
+    /// Maps a [Stream] back to the version 0 layout, if that layout can
+    /// represent all of its state.
+    pub fn from_stream(stream: &Stream) -> Option<StreamV0> {
+        let legacy = StreamV0 {
+            mint: stream.mint,
+            bump: stream.bump,
+            revoker: stream.revoker,
+            crate_token: stream.crate_token,
+            underlying_mint: stream.underlying_mint,
+            underlying_tokens: stream.underlying_tokens,
+            initial_amount: stream.initial_amount,
+            redeemed_amount: stream.redeemed_amount,
+            start_ts: stream.start_ts,
+            cliff_ts: stream.cliff_ts,
+            end_ts: stream.end_ts,
+        };
+        if legacy.migrate() != *stream {
+            return None;
+        }
+        Some(legacy)
+    }
+}
+
+/// A [Stream] account loaded from either the current layout or the
+/// [StreamV0] layout, so that instructions accept [Stream]s which have not
+/// been migrated via [crate::venko::migrate_stream].
+///
+/// A version 0 account is written back in the [StreamV0] layout. Changes
+/// which that layout cannot represent fail with
+/// [ErrorCode::StreamMigrationRequired].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct VersionedStream {
+    stream: Stream,
+    legacy: bool,
+}
+
+impl VersionedStream {
+    /// Returns true if the account has the [StreamV0] layout.
+    pub fn is_legacy(&self) -> bool {
+        self.legacy
+    }
+}
+
+impl Deref for VersionedStream {
+    type Target = Stream;
+
+    fn deref(&self) -> &Stream {
+        &self.stream
+    }
+}
+
+impl DerefMut for VersionedStream {
+    fn deref_mut(&mut self) -> &mut Stream {
+        &mut self.stream
+    }
+}
+
+impl Owner for VersionedStream {
+    fn owner() -> Pubkey {
+        crate::ID
+    }
+}
+
+impl AccountDeserialize for VersionedStream {
+    fn try_deserialize(buf: &mut &[u8]) -> std::result::Result<Self, ProgramError> {
+        if buf.len() == StreamV0::LEN {
+            // version 0 accounts share the discriminator of the current layout
+            if buf[..8] != Stream::discriminator() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            return Self::try_deserialize_unchecked(buf);
+        }
+        Ok(VersionedStream {
+            stream: Stream::try_deserialize(buf)?,
+            legacy: false,
+        })
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> std::result::Result<Self, ProgramError> {
+        if buf.len() == StreamV0::LEN {
+            return Ok(VersionedStream {
+                stream: StreamV0::try_from_slice(&buf[8..])?.migrate(),
+                legacy: true,
+            });
+        }
+        Ok(VersionedStream {
+            stream: Stream::try_deserialize_unchecked(buf)?,
+            legacy: false,
+        })
+    }
+}
+
+impl AccountSerialize for VersionedStream {
+    fn try_serialize<W: Write>(&self, writer: &mut W) -> std::result::Result<(), ProgramError> {
+        if !self.legacy {
+            return self.stream.try_serialize(writer);
+        }
+        let legacy = StreamV0::from_stream(&self.stream)
+            .ok_or_else(|| ProgramError::from(ErrorCode::StreamMigrationRequired))?;
+        writer.write_all(&Stream::discriminator())?;
+        legacy.serialize(writer)?;
+        Ok(())
+    }
+}
+
 impl Stream {
+    /// The current version of the [Stream] account layout.
+    ///
+    /// Version 0 accounts were created before [Stream::version] was added and
+    /// have the [StreamV0] layout. They must be migrated with
+    /// [crate::venko::migrate_stream].
+    pub const VERSION: u8 = 1;
+
+    /// The size of a [Stream] account, including the discriminator.
+    pub const LEN: usize = 773;
+
+    /// Returns true if the [Stream] tokens may be redeemed by any holder.
+    pub fn is_transferable(&self) -> bool {
+        self.beneficiary == Pubkey::default()
+    }
+
+    /// Checks that the [Stream] is [StreamStatus::Active], i.e. that its
+    /// schedule or tokens may still be changed.
+    pub fn assert_active(&self) -> ProgramResult {
+        match self.status {
+            StreamStatus::Active => Ok(()),
+            StreamStatus::Revoked => Err(ErrorCode::AlreadyRevoked.into()),
+            StreamStatus::Completed => Err(ErrorCode::StreamCompleted.into()),
+            StreamStatus::Cancelled => Err(ErrorCode::StreamCancelled.into()),
+            StreamStatus::Rejected => Err(ErrorCode::StreamRejected.into()),
+            StreamStatus::Pending => Err(ErrorCode::StreamPending.into()),
+        }
+    }
+
+    /// Checks that the [Stream] has not finished, i.e. that it is
+    /// [StreamStatus::Active] or [StreamStatus::Pending], so its roles and
+    /// metadata may still be changed.
+    pub fn assert_not_finished(&self) -> ProgramResult {
+        match self.status {
+            StreamStatus::Active | StreamStatus::Pending => Ok(()),
+            StreamStatus::Revoked => Err(ErrorCode::AlreadyRevoked.into()),
+            StreamStatus::Completed => Err(ErrorCode::StreamCompleted.into()),
+            StreamStatus::Cancelled => Err(ErrorCode::StreamCancelled.into()),
+            StreamStatus::Rejected => Err(ErrorCode::StreamRejected.into()),
+        }
+    }
+
+    /// Checks that the [Stream] has finished and may be closed.
+    pub fn assert_finished(&self) -> ProgramResult {
+        match self.status {
+            StreamStatus::Active => Err(ErrorCode::StreamNotDrained.into()),
+            StreamStatus::Pending => Err(ErrorCode::StreamPending.into()),
+            StreamStatus::Revoked
+            | StreamStatus::Completed
+            | StreamStatus::Cancelled
+            | StreamStatus::Rejected => Ok(()),
+        }
+    }
+
+    /// Checks that tokens may still be redeemed from the [Stream].
+    pub fn assert_redeemable(&self) -> ProgramResult {
+        match self.status {
+            StreamStatus::Active | StreamStatus::Revoked => Ok(()),
+            StreamStatus::Completed => Err(ErrorCode::StreamCompleted.into()),
+            StreamStatus::Cancelled => Err(ErrorCode::StreamCancelled.into()),
+            StreamStatus::Rejected => Err(ErrorCode::StreamRejected.into()),
+            StreamStatus::Pending => Err(ErrorCode::StreamPending.into()),
+        }
+    }
+
+    /// Starts a [StreamStatus::Pending] [Stream] at the given ts, converting
+    /// its relative schedule and [Self::cancel_until_ts] to absolute times.
+    pub fn accept(&mut self, current_ts: i64) -> Option<()> {
+        if self.cancel_until_ts != 0 {
+            self.cancel_until_ts = self.cancel_until_ts.checked_add(current_ts)?;
+        }
+        self.start_ts = self.start_ts.checked_add(current_ts)?;
+        self.cliff_ts = self.cliff_ts.checked_add(current_ts)?;
+        self.end_ts = self.end_ts.checked_add(current_ts)?;
+        for breakpoint in self
+            .breakpoints
+            .iter_mut()
+            .take(self.num_breakpoints.into())
+        {
+            breakpoint.ts = breakpoint.ts.checked_add(current_ts)?;
+        }
+        self.status = StreamStatus::Active;
+        Some(())
+    }
+
+    /// Checks that the `owner` of an author fee destination is the
+    /// [Self::author_fee_to], if the [Stream] charges fees.
+    pub fn assert_author_fee_owner(&self, owner: Pubkey) -> ProgramResult {
+        if self.author_fee_to != Pubkey::default() && owner != self.author_fee_to {
+            return Err(ErrorCode::InvalidFeeDestination.into());
+        }
+        Ok(())
+    }
+
+    /// Returns true if the [Self::creator] may still cancel the [Stream] at
+    /// the given ts.
+    pub fn is_cancellable(&self, current_ts: i64) -> bool {
+        current_ts < self.cancel_until_ts
+    }
+
+    /// Records the redemption of tokens, completing the [Stream] once all of
+    /// its tokens have been redeemed.
+    pub fn record_redemption(&mut self, amount: u64) -> Option<()> {
+        self.redeemed_amount = self.redeemed_amount.checked_add(amount)?;
+        if self.redeemed_amount == self.initial_amount {
+            self.status = StreamStatus::Completed;
+        }
+        Some(())
+    }
+
     /// Computes the amount of tokens available for withdrawal.
     /// The `remaining_amount` should be the total supply of the [Stream] token.
     pub fn available_for_withdrawal(&self, current_ts: i64, remaining_amount: u64) -> Option<u64> {
         Some(self.outstanding_released(current_ts)?.min(remaining_amount))
     }
 
+    /// Computes the amount of tokens available for withdrawal by a single
+    /// holder of [Stream] tokens.
+    ///
+    /// Each holder may only withdraw their pro-rata share of the released
+    /// tokens, based on the `holder_balance` of [Stream] tokens in the account
+    /// being redeemed from and the `holder_redeemed` amount recorded by the
+    /// holder's [HolderClaim].
+    pub fn holder_available_for_withdrawal(
+        &self,
+        current_ts: i64,
+        remaining_amount: u64,
+        holder_balance: u64,
+        holder_redeemed: u64,
+    ) -> Option<u64> {
+        let holder_total = holder_balance.checked_add(holder_redeemed)?;
+        let holder_released = (holder_total as u128)
+            .checked_mul(self.total_released(current_ts)?.into())?
+            .checked_div(self.initial_amount.into())?
+            .to_u64()?;
+
+        // the holder may have transferred away tokens whose release they
+        // have already redeemed
+        let holder_outstanding = holder_released.saturating_sub(holder_redeemed);
+        Some(
+            holder_outstanding
+                .min(holder_balance)
+                .min(self.available_for_withdrawal(current_ts, remaining_amount)?),
+        )
+    }
+
     /// The amount of outstanding locked tokens released.
     pub fn outstanding_released(&self, current_ts: i64) -> Option<u64> {
         self.total_released(current_ts)?
@@ -55,7 +594,10 @@ impl Stream {
     /// Returns the total released amount up to the given ts, assuming zero
     /// withdrawals and zero funds sent to other programs.
     pub fn total_released(&self, current_ts: i64) -> Option<u64> {
-        if current_ts <= self.cliff_ts {
+        // Nothing is released after a revocation or while paused.
+        let current_ts = self.schedule_ts(current_ts)?;
+
+        if current_ts < self.cliff_ts {
             return Some(0);
         }
 
@@ -63,23 +605,154 @@ impl Stream {
             return Some(self.initial_amount);
         }
 
+        if self.num_breakpoints > 0 {
+            return self.breakpoints_released(current_ts);
+        }
+
+        // The cliff amount is released in full at the cliff. The rest of the
+        // tokens are then released linearly from the cliff until the end.
+        let (linear_start_ts, linear_amount) = if self.cliff_amount == 0 {
+            (self.start_ts, self.initial_amount)
+        } else {
+            (
+                self.cliff_ts,
+                self.initial_amount.checked_sub(self.cliff_amount)?,
+            )
+        };
+
         // Signed division not supported.
         let current_ts = current_ts.to_u64()?;
-        let start_ts = self.start_ts.to_u64()?;
+        let start_ts = linear_start_ts.to_u64()?;
         let end_ts = self.end_ts.to_u64()?;
 
         if current_ts <= start_ts {
-            return Some(0);
+            return Some(self.cliff_amount);
         }
 
         if current_ts >= end_ts {
             return Some(self.initial_amount);
         }
 
-        (current_ts.checked_sub(start_ts)? as u128)
-            .checked_mul(self.initial_amount.into())?
+        // Only count periods which have fully elapsed.
+        let elapsed = current_ts.checked_sub(start_ts)?;
+        let elapsed = if self.period > 0 {
+            elapsed.checked_sub(elapsed.checked_rem(self.period.to_u64()?)?)?
+        } else {
+            elapsed
+        };
+
+        let linear_released = (elapsed as u128)
+            .checked_mul(linear_amount.into())?
             .checked_div(end_ts.checked_sub(start_ts)?.into())?
-            .to_u64()
+            .to_u64()?;
+        self.cliff_amount.checked_add(linear_released)
+    }
+
+    /// Converts the given ts to a point on the release schedule. The ts is
+    /// capped to the time of any past or scheduled revocation and to the
+    /// start of the current pause, then shifted back by the time spent paused.
+    fn schedule_ts(&self, current_ts: i64) -> Option<i64> {
+        let mut cutoff_ts = current_ts;
+        if self.revoked_at != 0 {
+            cutoff_ts = cutoff_ts.min(self.revoked_at);
+        }
+        if self.scheduled_revoke_ts != 0 {
+            cutoff_ts = cutoff_ts.min(self.scheduled_revoke_ts);
+        }
+        if self.paused_at != 0 {
+            cutoff_ts = cutoff_ts.min(self.paused_at);
+        }
+        cutoff_ts.checked_sub(self.paused_duration)
+    }
+
+    /// Resumes the [Stream], shifting the schedule by the time spent paused.
+    /// Returns the duration of the pause.
+    ///
+    /// Only the time spent paused before a scheduled revocation is counted,
+    /// since the schedule stops at the revocation either way.
+    pub fn resume(&mut self, current_ts: i64) -> Option<i64> {
+        let mut resume_ts = current_ts;
+        if self.scheduled_revoke_ts != 0 {
+            resume_ts = resume_ts.min(self.scheduled_revoke_ts.max(self.paused_at));
+        }
+        let pause_duration = resume_ts.checked_sub(self.paused_at)?;
+        self.paused_duration = self.paused_duration.checked_add(pause_duration)?;
+        self.paused_at = 0;
+        Some(pause_duration)
+    }
+
+    /// Adds tokens to the [Stream]. The new tokens follow the same schedule
+    /// as the existing ones: the cliff amount and any breakpoints are scaled
+    /// proportionally to the new [Self::initial_amount].
+    pub fn top_up(&mut self, amount: u64) -> Option<()> {
+        let prev_amount = self.initial_amount;
+        let next_amount = prev_amount.checked_add(amount)?;
+
+        let scale = |value: u64| -> Option<u64> {
+            (value as u128)
+                .checked_mul(next_amount.into())?
+                .checked_div(prev_amount.into())?
+                .to_u64()
+        };
+        self.cliff_amount = scale(self.cliff_amount)?;
+        for breakpoint in self
+            .breakpoints
+            .iter_mut()
+            .take(self.num_breakpoints.into())
+        {
+            breakpoint.amount = scale(breakpoint.amount)?;
+        }
+        self.initial_amount = next_amount;
+
+        Some(())
+    }
+
+    /// Returns the total released amount up to the given ts by interpolating
+    /// between the [Self::breakpoints].
+    fn breakpoints_released(&self, current_ts: i64) -> Option<u64> {
+        let breakpoints = self.breakpoints.get(..self.num_breakpoints.into())?;
+
+        let next_index = match breakpoints.iter().position(|bp| bp.ts > current_ts) {
+            // nothing is released before the first breakpoint
+            Some(0) => return Some(0),
+            Some(index) => index,
+            None => return Some(breakpoints.last()?.amount),
+        };
+        let prev = breakpoints.get(next_index.checked_sub(1)?)?;
+        let next = breakpoints.get(next_index)?;
+
+        let elapsed = current_ts.checked_sub(prev.ts)?.to_u64()?;
+        let duration = next.ts.checked_sub(prev.ts)?.to_u64()?;
+        let segment_released = (elapsed as u128)
+            .checked_mul(next.amount.checked_sub(prev.amount)?.into())?
+            .checked_div(duration.into())?
+            .to_u64()?;
+        prev.amount.checked_add(segment_released)
+    }
+}
+
+impl StreamIndex {
+    /// Reserves the next position in the index.
+    pub fn next_position(&mut self) -> Option<u64> {
+        let position = self.count;
+        self.count = position.checked_add(1)?;
+        Some(position)
+    }
+}
+
+impl VestingDistributor {
+    /// Returns the total amount of an `allocation` released up to the given ts.
+    /// Each allocation follows the same schedule as a [Stream] of that amount.
+    pub fn total_released(&self, allocation: u64, current_ts: i64) -> Option<u64> {
+        Stream {
+            initial_amount: allocation,
+            start_ts: self.start_ts,
+            cliff_ts: self.cliff_ts,
+            end_ts: self.end_ts,
+            period: self.period,
+            ..Stream::default()
+        }
+        .total_released(current_ts)
     }
 }
 
@@ -117,4 +790,671 @@ mod tests {
         let amt = release.total_released(150_000).unwrap();
         assert_eq!(amt, 500_000);
     }
+
+    #[test]
+    fn test_periodic_unlock_at_step_boundaries() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.end_ts = 200_000;
+        release.period = 25_000;
+        release.initial_amount = 1_000_000;
+        assert_eq!(release.total_released(100_000).unwrap(), 0);
+        assert_eq!(release.total_released(125_000).unwrap(), 250_000);
+        assert_eq!(release.total_released(150_000).unwrap(), 500_000);
+        assert_eq!(release.total_released(175_000).unwrap(), 750_000);
+        assert_eq!(release.total_released(200_000).unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn test_periodic_unlock_between_step_boundaries() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.end_ts = 200_000;
+        release.period = 25_000;
+        release.initial_amount = 1_000_000;
+        assert_eq!(release.total_released(124_999).unwrap(), 0);
+        assert_eq!(release.total_released(125_001).unwrap(), 250_000);
+        assert_eq!(release.total_released(160_000).unwrap(), 500_000);
+        assert_eq!(release.total_released(199_999).unwrap(), 750_000);
+    }
+
+    #[test]
+    fn test_periodic_unlock_uneven_last_period() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.end_ts = 200_000;
+        release.period = 30_000;
+        release.initial_amount = 1_000_000;
+        assert_eq!(release.total_released(190_000).unwrap(), 900_000);
+        assert_eq!(release.total_released(200_000).unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn test_periodic_unlock_available_for_withdrawal() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.end_ts = 200_000;
+        release.period = 25_000;
+        release.initial_amount = 1_000_000;
+        release.redeemed_amount = 250_000;
+        assert_eq!(
+            release.available_for_withdrawal(140_000, 750_000).unwrap(),
+            0
+        );
+        assert_eq!(
+            release.available_for_withdrawal(150_000, 750_000).unwrap(),
+            250_000
+        );
+    }
+
+    #[test]
+    fn test_cliff_amount_before_cliff() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.cliff_ts = 125_000;
+        release.end_ts = 200_000;
+        release.initial_amount = 1_000_000;
+        release.cliff_amount = 250_000;
+        assert_eq!(release.total_released(124_999).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_cliff_amount_at_cliff() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.cliff_ts = 125_000;
+        release.end_ts = 200_000;
+        release.initial_amount = 1_000_000;
+        release.cliff_amount = 250_000;
+        assert_eq!(release.total_released(125_000).unwrap(), 250_000);
+    }
+
+    #[test]
+    fn test_cliff_amount_then_linear() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.cliff_ts = 125_000;
+        release.end_ts = 200_000;
+        release.initial_amount = 1_000_000;
+        release.cliff_amount = 250_000;
+        assert_eq!(release.total_released(162_500).unwrap(), 625_000);
+        assert_eq!(release.total_released(200_000).unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn test_cliff_amount_then_periodic() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.cliff_ts = 125_000;
+        release.end_ts = 200_000;
+        release.period = 25_000;
+        release.initial_amount = 1_000_000;
+        release.cliff_amount = 250_000;
+        assert_eq!(release.total_released(149_999).unwrap(), 250_000);
+        assert_eq!(release.total_released(150_000).unwrap(), 500_000);
+        assert_eq!(release.total_released(199_999).unwrap(), 750_000);
+    }
+
+    fn set_breakpoints(release: &mut Stream, breakpoints: &[Breakpoint]) {
+        release.num_breakpoints = breakpoints.len() as u8;
+        release.breakpoints[..breakpoints.len()].copy_from_slice(breakpoints);
+    }
+
+    #[test]
+    fn test_breakpoints_unlock() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.end_ts = 400_000;
+        release.initial_amount = 1_000_000;
+        set_breakpoints(
+            release,
+            &[
+                Breakpoint {
+                    ts: 100_000,
+                    amount: 0,
+                },
+                Breakpoint {
+                    ts: 200_000,
+                    amount: 200_000,
+                },
+                Breakpoint {
+                    ts: 400_000,
+                    amount: 1_000_000,
+                },
+            ],
+        );
+        assert_eq!(release.total_released(90_000).unwrap(), 0);
+        assert_eq!(release.total_released(150_000).unwrap(), 100_000);
+        assert_eq!(release.total_released(200_000).unwrap(), 200_000);
+        assert_eq!(release.total_released(300_000).unwrap(), 600_000);
+        assert_eq!(release.total_released(400_000).unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn test_breakpoints_lump_sum_at_first_breakpoint() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.end_ts = 300_000;
+        release.initial_amount = 1_000_000;
+        set_breakpoints(
+            release,
+            &[
+                Breakpoint {
+                    ts: 200_000,
+                    amount: 500_000,
+                },
+                Breakpoint {
+                    ts: 300_000,
+                    amount: 1_000_000,
+                },
+            ],
+        );
+        assert_eq!(release.total_released(199_999).unwrap(), 0);
+        assert_eq!(release.total_released(200_000).unwrap(), 500_000);
+        assert_eq!(release.total_released(250_000).unwrap(), 750_000);
+    }
+
+    #[test]
+    fn test_breakpoints_flat_segment() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.end_ts = 300_000;
+        release.initial_amount = 1_000_000;
+        set_breakpoints(
+            release,
+            &[
+                Breakpoint {
+                    ts: 100_000,
+                    amount: 0,
+                },
+                Breakpoint {
+                    ts: 150_000,
+                    amount: 400_000,
+                },
+                Breakpoint {
+                    ts: 250_000,
+                    amount: 400_000,
+                },
+                Breakpoint {
+                    ts: 300_000,
+                    amount: 1_000_000,
+                },
+            ],
+        );
+        assert_eq!(release.total_released(200_000).unwrap(), 400_000);
+        assert_eq!(release.total_released(275_000).unwrap(), 700_000);
+    }
+
+    #[test]
+    fn test_top_up_linear() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.end_ts = 200_000;
+        release.initial_amount = 1_000_000;
+        release.top_up(1_000_000).unwrap();
+        assert_eq!(release.initial_amount, 2_000_000);
+        assert_eq!(release.total_released(150_000).unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn test_top_up_scales_cliff_and_breakpoints() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.cliff_ts = 125_000;
+        release.end_ts = 200_000;
+        release.initial_amount = 1_000_000;
+        release.cliff_amount = 250_000;
+        release.top_up(500_000).unwrap();
+        assert_eq!(release.cliff_amount, 375_000);
+        assert_eq!(release.total_released(125_000).unwrap(), 375_000);
+
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.end_ts = 300_000;
+        release.initial_amount = 1_000_000;
+        set_breakpoints(
+            release,
+            &[
+                Breakpoint {
+                    ts: 200_000,
+                    amount: 400_000,
+                },
+                Breakpoint {
+                    ts: 300_000,
+                    amount: 1_000_000,
+                },
+            ],
+        );
+        release.top_up(500_000).unwrap();
+        assert_eq!(release.breakpoints[0].amount, 600_000);
+        assert_eq!(release.breakpoints[1].amount, 1_500_000);
+        assert_eq!(release.breakpoints[2].amount, 0);
+    }
+
+    #[test]
+    fn test_revoked_stops_release() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.end_ts = 200_000;
+        release.initial_amount = 1_000_000;
+        release.revoked_at = 125_000;
+        assert_eq!(release.total_released(120_000).unwrap(), 200_000);
+        assert_eq!(release.total_released(150_000).unwrap(), 250_000);
+        assert_eq!(release.total_released(290_000).unwrap(), 250_000);
+    }
+
+    #[test]
+    fn test_scheduled_revocation_stops_release() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.end_ts = 200_000;
+        release.initial_amount = 1_000_000;
+        release.scheduled_revoke_ts = 150_000;
+        assert_eq!(release.total_released(125_000).unwrap(), 250_000);
+        assert_eq!(release.total_released(175_000).unwrap(), 500_000);
+
+        release.revoked_at = 140_000;
+        assert_eq!(release.total_released(175_000).unwrap(), 400_000);
+    }
+
+    #[test]
+    fn test_paused_stops_release() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.end_ts = 200_000;
+        release.initial_amount = 1_000_000;
+        release.paused_at = 150_000;
+        assert_eq!(release.total_released(150_000).unwrap(), 500_000);
+        assert_eq!(release.total_released(290_000).unwrap(), 500_000);
+    }
+
+    #[test]
+    fn test_resumed_shifts_schedule() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.end_ts = 200_000;
+        release.initial_amount = 1_000_000;
+        release.paused_duration = 50_000;
+        assert_eq!(release.total_released(150_000).unwrap(), 0);
+        assert_eq!(release.total_released(200_000).unwrap(), 500_000);
+        assert_eq!(release.total_released(249_999).unwrap(), 999_990);
+        assert_eq!(release.total_released(250_000).unwrap(), 1_000_000);
+
+        // paused again after resuming
+        release.paused_at = 225_000;
+        assert_eq!(release.total_released(240_000).unwrap(), 750_000);
+    }
+
+    #[test]
+    fn test_resume_after_scheduled_revocation() {
+        let release = &mut Stream::default();
+        release.start_ts = 0;
+        release.end_ts = 100;
+        release.initial_amount = 100;
+        release.scheduled_revoke_ts = 50;
+        assert_eq!(release.total_released(70).unwrap(), 50);
+
+        // paused after the revocation: nothing is added to the schedule
+        release.paused_at = 60;
+        assert_eq!(release.resume(90).unwrap(), 0);
+        assert_eq!(release.total_released(90).unwrap(), 50);
+        assert_eq!(release.outstanding_released(90).unwrap(), 50);
+
+        // paused across the revocation: only the time before it is added
+        release.paused_at = 40;
+        assert_eq!(release.total_released(60).unwrap(), 40);
+        assert_eq!(release.resume(60).unwrap(), 10);
+        assert_eq!(release.total_released(60).unwrap(), 40);
+    }
+
+    #[test]
+    fn test_holder_pro_rata_share() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.end_ts = 200_000;
+        release.initial_amount = 1_000_000;
+
+        // two holders with 600_000 and 400_000 tokens, halfway through
+        let amt = release
+            .holder_available_for_withdrawal(150_000, 1_000_000, 600_000, 0)
+            .unwrap();
+        assert_eq!(amt, 300_000);
+        let amt = release
+            .holder_available_for_withdrawal(150_000, 1_000_000, 400_000, 0)
+            .unwrap();
+        assert_eq!(amt, 200_000);
+
+        // the first holder redeems, which does not affect the second holder
+        release.redeemed_amount = 300_000;
+        let amt = release
+            .holder_available_for_withdrawal(150_000, 700_000, 300_000, 300_000)
+            .unwrap();
+        assert_eq!(amt, 0);
+        let amt = release
+            .holder_available_for_withdrawal(150_000, 700_000, 400_000, 0)
+            .unwrap();
+        assert_eq!(amt, 200_000);
+
+        // later, the first holder gets their share of the new tokens
+        let amt = release
+            .holder_available_for_withdrawal(175_000, 700_000, 300_000, 300_000)
+            .unwrap();
+        assert_eq!(amt, 150_000);
+    }
+
+    #[test]
+    fn test_holder_share_after_transfer_out() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.end_ts = 200_000;
+        release.initial_amount = 1_000_000;
+        release.redeemed_amount = 500_000;
+
+        // redeemed 500_000, then transferred away 400_000 of the remainder
+        let amt = release
+            .holder_available_for_withdrawal(150_000, 500_000, 100_000, 500_000)
+            .unwrap();
+        assert_eq!(amt, 0);
+    }
+
+    #[test]
+    fn test_holder_share_after_moving_tokens() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.end_ts = 200_000;
+        release.initial_amount = 1_000_000;
+
+        // holder A with 600_000 tokens redeems their half of them
+        let a_redeemed = 300_000;
+        release.redeemed_amount = a_redeemed;
+
+        // A moves the remaining 300_000 to a new account, whose redemptions
+        // are still tracked by A's claim
+        let amt = release
+            .holder_available_for_withdrawal(150_000, 700_000, 300_000, a_redeemed)
+            .unwrap();
+        assert_eq!(amt, 0);
+
+        // holder C with 400_000 tokens keeps their share
+        let amt = release
+            .holder_available_for_withdrawal(150_000, 700_000, 400_000, 0)
+            .unwrap();
+        assert_eq!(amt, 200_000);
+    }
+
+    #[test]
+    fn test_holder_share_after_moving_tokens_to_new_owner() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.end_ts = 200_000;
+        release.initial_amount = 1_000_000;
+
+        // holder A with 600_000 tokens redeems their half of them
+        release.redeemed_amount = 300_000;
+
+        // A moves the remaining 300_000 to a new owner, whose claim has not
+        // redeemed anything, so their half may be redeemed again
+        let amt = release
+            .holder_available_for_withdrawal(150_000, 700_000, 300_000, 0)
+            .unwrap();
+        assert_eq!(amt, 150_000);
+        release.redeemed_amount = 450_000;
+
+        // which leaves holder C with 400_000 tokens short until more tokens
+        // are released
+        let amt = release
+            .holder_available_for_withdrawal(150_000, 550_000, 400_000, 0)
+            .unwrap();
+        assert_eq!(amt, 50_000);
+        let amt = release
+            .holder_available_for_withdrawal(175_000, 550_000, 400_000, 0)
+            .unwrap();
+        assert_eq!(amt, 300_000);
+    }
+
+    #[test]
+    fn test_holder_share_at_end() {
+        let release = &mut Stream::default();
+        release.start_ts = 100_000;
+        release.end_ts = 200_000;
+        release.initial_amount = 1_000_000;
+        release.redeemed_amount = 300_000;
+        let amt = release
+            .holder_available_for_withdrawal(200_000, 700_000, 300_000, 300_000)
+            .unwrap();
+        assert_eq!(amt, 300_000);
+    }
+
+    #[test]
+    fn test_distributor_follows_stream_schedule() {
+        let distributor = VestingDistributor {
+            start_ts: 100_000,
+            cliff_ts: 125_000,
+            end_ts: 200_000,
+            period: 25_000,
+            ..VestingDistributor::default()
+        };
+        assert_eq!(distributor.total_released(1_000, 124_999).unwrap(), 0);
+        assert_eq!(distributor.total_released(1_000, 150_000).unwrap(), 500);
+        assert_eq!(distributor.total_released(3_000, 150_000).unwrap(), 1_500);
+        assert_eq!(distributor.total_released(1_000, 200_000).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn test_status_transitions() {
+        let release = &mut Stream::default();
+        release.initial_amount = 1_000;
+        assert_eq!(release.assert_active(), Ok(()));
+
+        release.record_redemption(400).unwrap();
+        assert_eq!(release.status, StreamStatus::Active);
+        release.record_redemption(600).unwrap();
+        assert_eq!(release.status, StreamStatus::Completed);
+        assert_eq!(
+            release.assert_active(),
+            Err(ErrorCode::StreamCompleted.into())
+        );
+        assert_eq!(
+            release.assert_redeemable(),
+            Err(ErrorCode::StreamCompleted.into())
+        );
+
+        assert_eq!(
+            release.assert_not_finished(),
+            Err(ErrorCode::StreamCompleted.into())
+        );
+        assert_eq!(release.assert_finished(), Ok(()));
+
+        release.status = StreamStatus::Revoked;
+        assert_eq!(
+            release.assert_active(),
+            Err(ErrorCode::AlreadyRevoked.into())
+        );
+        assert_eq!(release.assert_redeemable(), Ok(()));
+        assert_eq!(
+            release.assert_not_finished(),
+            Err(ErrorCode::AlreadyRevoked.into())
+        );
+
+        release.status = StreamStatus::Pending;
+        assert_eq!(release.assert_not_finished(), Ok(()));
+        assert_eq!(
+            release.assert_finished(),
+            Err(ErrorCode::StreamPending.into())
+        );
+
+        release.status = StreamStatus::Active;
+        assert_eq!(
+            release.assert_finished(),
+            Err(ErrorCode::StreamNotDrained.into())
+        );
+    }
+
+    #[test]
+    fn test_stream_layout() {
+        let stream = Stream {
+            version: Stream::VERSION,
+            ..Stream::default()
+        };
+        let data = stream.try_to_vec().unwrap();
+        assert_eq!(8 + data.len(), Stream::LEN);
+        assert_eq!(
+            8 + StreamV0::default().try_to_vec().unwrap().len(),
+            StreamV0::LEN
+        );
+    }
+
+    #[test]
+    fn test_migrate_v0_stream() {
+        // a version 0 account, encoded field by field
+        let mut data = Stream::discriminator().to_vec();
+        data.extend_from_slice(&[1; 32]); // mint
+        data.push(254); // bump
+        data.extend_from_slice(&[2; 32]); // revoker
+        data.extend_from_slice(&[3; 32]); // crate_token
+        data.extend_from_slice(&[4; 32]); // underlying_mint
+        data.extend_from_slice(&[5; 32]); // underlying_tokens
+        data.extend_from_slice(&1_000_000_u64.to_le_bytes()); // initial_amount
+        data.extend_from_slice(&250_000_u64.to_le_bytes()); // redeemed_amount
+        data.extend_from_slice(&100_000_i64.to_le_bytes()); // start_ts
+        data.extend_from_slice(&125_000_i64.to_le_bytes()); // cliff_ts
+        data.extend_from_slice(&200_000_i64.to_le_bytes()); // end_ts
+        assert_eq!(data.len(), StreamV0::LEN);
+
+        let legacy = StreamV0::try_from_slice(&data[8..]).unwrap();
+        let stream = legacy.migrate();
+        assert_eq!(stream.mint, Pubkey::new_from_array([1; 32]));
+        assert_eq!(stream.bump, 254);
+        assert_eq!(stream.revoker, Pubkey::new_from_array([2; 32]));
+        assert_eq!(stream.crate_token, Pubkey::new_from_array([3; 32]));
+        assert_eq!(stream.underlying_mint, Pubkey::new_from_array([4; 32]));
+        assert_eq!(stream.underlying_tokens, Pubkey::new_from_array([5; 32]));
+        assert_eq!(stream.initial_amount, 1_000_000);
+        assert_eq!(stream.redeemed_amount, 250_000);
+        assert_eq!(stream.start_ts, 100_000);
+        assert_eq!(stream.cliff_ts, 125_000);
+        assert_eq!(stream.end_ts, 200_000);
+        assert_eq!(stream.status, StreamStatus::Active);
+        assert_eq!(stream.version, Stream::VERSION);
+        assert_eq!(stream.creator, stream.revoker);
+        assert_eq!(stream.total_released(150_000).unwrap(), 500_000);
+
+        let mut migrated = vec![];
+        stream.try_serialize(&mut migrated).unwrap();
+        assert_eq!(migrated.len(), Stream::LEN);
+        let stream = Stream::try_deserialize(&mut &migrated[..]).unwrap();
+        assert_eq!(stream.end_ts, 200_000);
+
+        // fully redeemed or revoked
+        let completed = StreamV0 {
+            initial_amount: 1_000_000,
+            redeemed_amount: 1_000_000,
+            ..legacy
+        };
+        assert_eq!(completed.migrate().status, StreamStatus::Completed);
+    }
+
+    #[test]
+    fn test_load_versioned_stream() {
+        let legacy = StreamV0 {
+            mint: Pubkey::new_from_array([1; 32]),
+            revoker: Pubkey::new_from_array([2; 32]),
+            initial_amount: 1_000_000,
+            redeemed_amount: 250_000,
+            start_ts: 100_000,
+            cliff_ts: 100_000,
+            end_ts: 200_000,
+            ..StreamV0::default()
+        };
+        let mut data = Stream::discriminator().to_vec();
+        data.extend_from_slice(&legacy.try_to_vec().unwrap());
+
+        // version 0 accounts are loaded as migrated
+        let mut stream = VersionedStream::try_deserialize(&mut &data[..]).unwrap();
+        assert!(stream.is_legacy());
+        assert_eq!(*stream, legacy.migrate());
+
+        // and written back in the version 0 layout
+        stream.redeemed_amount = 1_000_000;
+        stream.status = StreamStatus::Completed;
+        let mut written = vec![];
+        stream.try_serialize(&mut written).unwrap();
+        assert_eq!(written.len(), StreamV0::LEN);
+        let reloaded = VersionedStream::try_deserialize(&mut &written[..]).unwrap();
+        assert_eq!(reloaded, stream);
+
+        // unless the change does not fit the version 0 layout
+        stream.paused_at = 150_000;
+        assert_eq!(
+            stream.try_serialize(&mut vec![]),
+            Err(ErrorCode::StreamMigrationRequired.into())
+        );
+
+        // current accounts are loaded as is
+        let mut current = vec![];
+        stream.paused_at = 0;
+        (*stream).try_serialize(&mut current).unwrap();
+        let stream = VersionedStream::try_deserialize(&mut &current[..]).unwrap();
+        assert!(!stream.is_legacy());
+        assert_eq!(current.len(), Stream::LEN);
+    }
+
+    #[test]
+    fn test_cancellation_window() {
+        let release = &mut Stream::default();
+        assert!(!release.is_cancellable(0));
+
+        release.cancel_until_ts = 100;
+        assert!(release.is_cancellable(99));
+        assert!(!release.is_cancellable(100));
+
+        release.status = StreamStatus::Cancelled;
+        assert_eq!(
+            release.assert_redeemable(),
+            Err(ErrorCode::StreamCancelled.into())
+        );
+    }
+
+    #[test]
+    fn test_accept_pending() {
+        let release = &mut Stream::default();
+        release.status = StreamStatus::Pending;
+        release.initial_amount = 1_000;
+        release.cliff_ts = 100;
+        release.end_ts = 1_000;
+        release.num_breakpoints = 1;
+        release.breakpoints[0] = Breakpoint {
+            ts: 1_000,
+            amount: 1_000,
+        };
+        assert_eq!(
+            release.assert_redeemable(),
+            Err(ErrorCode::StreamPending.into())
+        );
+
+        release.cancel_until_ts = 10;
+        release.accept(50_000).unwrap();
+        assert_eq!(release.status, StreamStatus::Active);
+        assert_eq!(release.cancel_until_ts, 50_010);
+        assert_eq!(release.start_ts, 50_000);
+        assert_eq!(release.cliff_ts, 50_100);
+        assert_eq!(release.end_ts, 51_000);
+        assert_eq!(release.breakpoints[0].ts, 51_000);
+        assert_eq!(release.total_released(50_050).unwrap(), 0);
+        assert_eq!(release.total_released(51_000).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn test_author_fee_owner() {
+        let release = &mut Stream::default();
+        let owner = Pubkey::new_unique();
+        assert_eq!(release.assert_author_fee_owner(owner), Ok(()));
+
+        release.author_fee_to = owner;
+        assert_eq!(release.assert_author_fee_owner(owner), Ok(()));
+        assert_eq!(
+            release.assert_author_fee_owner(Pubkey::default()),
+            Err(ErrorCode::InvalidFeeDestination.into())
+        );
+    }
 }
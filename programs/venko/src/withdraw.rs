@@ -0,0 +1,117 @@
+//! Withdrawals of the underlying tokens of a [Stream] from its Crate.
+
+use crate::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate_token::program::CrateToken as CrateTokenProgram;
+use crate_token::state::Fees;
+use crate_token::CrateToken;
+
+/// Accounts used to withdraw underlying tokens from the Crate of a [Stream].
+pub(crate) struct StreamWithdraw<'a, 'info> {
+    /// The [Stream], which is the withdraw authority of its Crate.
+    pub stream: &'a Account<'info, VersionedStream>,
+    /// The [Stream::crate_token].
+    pub crate_token: &'a Account<'info, CrateToken>,
+    /// The [Stream::underlying_tokens].
+    pub underlying_tokens: &'a Account<'info, TokenAccount>,
+    /// Destination of the withdrawn tokens.
+    pub destination_tokens: &'a Account<'info, TokenAccount>,
+    /// Destination of the author fees charged by the Crate.
+    pub author_fee_destination: &'a Account<'info, TokenAccount>,
+    /// Destination of the protocol fees charged by the Crate.
+    pub protocol_fee_destination: &'a Account<'info, TokenAccount>,
+    /// [crate_token] program.
+    pub crate_token_program: &'a Program<'info, CrateTokenProgram>,
+    /// SPL [anchor_spl::token] program.
+    pub token_program: &'a Program<'info, Token>,
+}
+
+impl<'a, 'info> StreamWithdraw<'a, 'info> {
+    /// Withdraws `amount` underlying tokens to the [Self::destination_tokens],
+    /// returning the fees charged by the Crate.
+    pub(crate) fn withdraw(&self, amount: u64) -> Result<Fees> {
+        let fees = self.crate_token.apply_withdraw_fee(amount)?;
+        self.withdraw_cpi(amount)?;
+        Ok(fees)
+    }
+
+    /// Withdraws `amount` underlying tokens to the [Self::destination_tokens]
+    /// without charging the Crate withdraw fee.
+    ///
+    /// The [Stream] is the fee setter of any Crate which charges fees, so the
+    /// fee is lifted for the duration of the withdrawal. The
+    /// [Self::crate_token] must be writable if it charges fees.
+    pub(crate) fn withdraw_without_fee(&self, amount: u64) -> ProgramResult {
+        let withdraw_fee_bps = self.crate_token.withdraw_fee_bps;
+        if withdraw_fee_bps == 0 {
+            return self.withdraw_cpi(amount);
+        }
+        self.set_withdraw_fee(0)?;
+        self.withdraw_cpi(amount)?;
+        self.set_withdraw_fee(withdraw_fee_bps)
+    }
+
+    /// Refunds `amount` underlying tokens to `destination_tokens` without
+    /// charging the Crate withdraw fee.
+    ///
+    /// Refunds return tokens which were never released to a holder, e.g. on
+    /// a revocation, cancellation or rejection, so they are not charged
+    /// fees. As no fee is charged, the `destination_tokens` also stand in
+    /// for the fee destinations.
+    pub(crate) fn refund(
+        stream: &'a Account<'info, VersionedStream>,
+        crate_token: &'a Account<'info, CrateToken>,
+        underlying_tokens: &'a Account<'info, TokenAccount>,
+        destination_tokens: &'a Account<'info, TokenAccount>,
+        crate_token_program: &'a Program<'info, CrateTokenProgram>,
+        token_program: &'a Program<'info, Token>,
+        amount: u64,
+    ) -> ProgramResult {
+        StreamWithdraw {
+            stream,
+            crate_token,
+            underlying_tokens,
+            destination_tokens,
+            author_fee_destination: destination_tokens,
+            protocol_fee_destination: destination_tokens,
+            crate_token_program,
+            token_program,
+        }
+        .withdraw_without_fee(amount)
+    }
+
+    fn withdraw_cpi(&self, amount: u64) -> ProgramResult {
+        let signer_seeds: &[&[&[u8]]] = stream_seeds!(self.stream);
+        crate_token::cpi::withdraw(
+            CpiContext::new_with_signer(
+                self.crate_token_program.to_account_info(),
+                crate_token::cpi::accounts::Withdraw {
+                    crate_token: self.crate_token.to_account_info(),
+                    crate_underlying: self.underlying_tokens.to_account_info(),
+                    withdraw_authority: self.stream.to_account_info(),
+                    withdraw_destination: self.destination_tokens.to_account_info(),
+                    author_fee_destination: self.author_fee_destination.to_account_info(),
+                    protocol_fee_destination: self.protocol_fee_destination.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )
+    }
+
+    fn set_withdraw_fee(&self, withdraw_fee_bps: u16) -> ProgramResult {
+        let signer_seeds: &[&[&[u8]]] = stream_seeds!(self.stream);
+        crate_token::cpi::set_withdraw_fee(
+            CpiContext::new_with_signer(
+                self.crate_token_program.to_account_info(),
+                crate_token::cpi::accounts::SetFees {
+                    crate_token: self.crate_token.to_account_info(),
+                    fee_setter: self.stream.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            withdraw_fee_bps,
+        )
+    }
+}
@@ -8,11 +8,19 @@ export type VenkoTypes = AnchorTypes<
   VenkoIDL,
   {
     stream: StreamData;
+    vestingDistributor: VestingDistributorData;
+    claimStatus: ClaimStatusData;
+    streamIndex: StreamIndexData;
+    streamIndexEntry: StreamIndexEntryData;
   }
 >;
 
 type Accounts = VenkoTypes["Accounts"];
 
 export type StreamData = Accounts["Stream"];
+export type VestingDistributorData = Accounts["VestingDistributor"];
+export type ClaimStatusData = Accounts["ClaimStatus"];
+export type StreamIndexData = Accounts["StreamIndex"];
+export type StreamIndexEntryData = Accounts["StreamIndexEntry"];
 
 export type VenkoProgram = VenkoTypes["Program"];
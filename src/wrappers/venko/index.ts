@@ -1,3 +1,5 @@
+export * from "./legacy";
 export * from "./math";
+export * from "./metadata";
 export * from "./pda";
 export * from "./venko";
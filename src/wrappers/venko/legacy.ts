@@ -0,0 +1,71 @@
+import { PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+
+import type { StreamData } from "../../programs/venko";
+
+/**
+ * Size of a Stream account, including the discriminator.
+ */
+export const STREAM_LEN = 773;
+
+/**
+ * Size of a version 0 Stream account, including the discriminator. Version 0
+ * accounts must be migrated to the current layout before most changes.
+ */
+export const STREAM_V0_LEN = 209;
+
+/**
+ * Decodes a version 0 Stream account as it will be after its migration.
+ * Its version remains zero until it has been migrated.
+ * @param data Account data, including the discriminator.
+ * @param defaults The current layout with every field zeroed.
+ * @returns
+ */
+export const decodeStreamV0 = (
+  data: Buffer,
+  defaults: StreamData
+): StreamData => {
+  let offset = 8;
+  const read = (length: number): Buffer => {
+    const value = data.slice(offset, offset + length);
+    offset += length;
+    return value;
+  };
+  const readKey = () => new PublicKey(read(32));
+  const readU64 = () => new BN(read(8), "le");
+  const readI64 = () => new BN(read(8), "le").fromTwos(64);
+
+  const mint = readKey();
+  const bump = read(1)[0] ?? 0;
+  const revoker = readKey();
+  const crateToken = readKey();
+  const underlyingMint = readKey();
+  const underlyingTokens = readKey();
+  const initialAmount = readU64();
+  const redeemedAmount = readU64();
+  const startTs = readI64();
+  const cliffTs = readI64();
+  const endTs = readI64();
+
+  // revoked and completed version 0 Streams both look fully redeemed
+  const completed =
+    !initialAmount.isZero() && redeemedAmount.gte(initialAmount);
+  return {
+    ...defaults,
+    mint,
+    bump,
+    revoker,
+    crateToken,
+    underlyingMint,
+    underlyingTokens,
+    initialAmount,
+    redeemedAmount,
+    startTs,
+    cliffTs,
+    endTs,
+    status: completed ? { completed: {} } : { active: {} },
+    // the creator was not recorded, so the revoker acts as creator
+    creator: revoker,
+    version: 0,
+  };
+};
@@ -1,26 +1,106 @@
 import { u64 } from "@saberhq/token-utils";
+import BN from "bn.js";
 
 import type { StreamData } from "../../programs/venko";
 
 /**
- * Computes the amount of tokens that may be redeemed on a Stream.
+ * Interpolates the total amount released between breakpoints.
+ * @param breakpoints
+ * @param nowTs
+ * @returns
+ */
+const computeBreakpointsReleased = (
+  breakpoints: StreamData["breakpoints"],
+  nowTs: u64
+): u64 => {
+  const nextIndex = breakpoints.findIndex((bp) => bp.ts.gt(nowTs));
+  if (nextIndex === 0) {
+    return new u64(0);
+  }
+  if (nextIndex === -1) {
+    return breakpoints[breakpoints.length - 1]?.amount ?? new u64(0);
+  }
+  const prev = breakpoints[nextIndex - 1];
+  const next = breakpoints[nextIndex];
+  if (!prev || !next) {
+    return new u64(0);
+  }
+  return new u64(
+    prev.amount.add(
+      nowTs
+        .sub(prev.ts)
+        .mul(next.amount.sub(prev.amount))
+        .div(next.ts.sub(prev.ts))
+    )
+  );
+};
+
+/**
+ * Computes the amount of tokens released but not yet redeemed on a Stream.
  * @param stream
+ * @param currentTS Current time, in seconds.
  * @returns
  */
-export const computeRedeemableAmount = (stream: StreamData): u64 => {
-  const nowTs = new u64(Math.floor(new Date().getTime() / 1_000));
-  const startTs = stream.startTs;
+const computeReleasedAmount = (stream: StreamData, currentTS: number): u64 => {
+  // nothing is released after a revocation or while paused
+  const cutoffTs = [stream.revokedAt, stream.scheduledRevokeTs, stream.pausedAt]
+    .filter((ts) => !ts.isZero())
+    .reduce((cutoff, ts) => BN.min(cutoff, ts), new BN(currentTS));
+  // the schedule is shifted by the time spent paused
+  const nowTs = new u64(cutoffTs.sub(stream.pausedDuration));
   const cliffTs = stream.cliffTs;
   const endTs = stream.endTs;
-  if (nowTs.lt(cliffTs) || nowTs.lt(startTs)) {
+  if (nowTs.lt(cliffTs) || nowTs.lt(stream.startTs)) {
     return new u64(0);
   }
   if (nowTs.gte(endTs)) {
     return stream.initialAmount.sub(stream.redeemedAmount);
   }
-  const max = nowTs
-    .sub(startTs)
-    .mul(stream.initialAmount)
-    .div(endTs.sub(startTs));
+  if (stream.numBreakpoints > 0) {
+    const released = computeBreakpointsReleased(
+      stream.breakpoints.slice(0, stream.numBreakpoints),
+      nowTs
+    );
+    return new u64(released.sub(stream.redeemedAmount));
+  }
+  // the cliff amount is released at the cliff, then the rest linearly
+  const hasCliffAmount = !stream.cliffAmount.isZero();
+  const startTs = hasCliffAmount ? cliffTs : stream.startTs;
+  const linearAmount = stream.initialAmount.sub(stream.cliffAmount);
+  const elapsed = nowTs.sub(startTs);
+  const steppedElapsed = stream.period.isZero()
+    ? elapsed
+    : elapsed.sub(elapsed.mod(stream.period));
+  const max = stream.cliffAmount.add(
+    steppedElapsed.mul(linearAmount).div(endTs.sub(startTs))
+  );
   return new u64(max.sub(stream.redeemedAmount));
 };
+
+/**
+ * Computes the amount of tokens that may be redeemed on a Stream.
+ * @param stream
+ * @param currentTS Current time, in seconds. Pass the on-chain clock for an
+ * exact result. Defaults to the local clock.
+ * @param underlyingAmount Balance of the Stream's underlying tokens. A
+ * revocation may withdraw released tokens, so pass this for an exact result.
+ * @returns
+ */
+export const computeRedeemableAmount = (
+  stream: StreamData,
+  currentTS = Math.floor(new Date().getTime() / 1_000),
+  underlyingAmount?: BN
+): u64 => {
+  // nothing may be redeemed before acceptance or while the creator may
+  // still cancel
+  if (
+    "pending" in stream.status ||
+    stream.cancelUntilTs.gt(new BN(currentTS))
+  ) {
+    return new u64(0);
+  }
+  const released = computeReleasedAmount(stream, currentTS);
+  return underlyingAmount
+    ? new u64(BN.min(released, underlyingAmount))
+    : released;
+};
@@ -0,0 +1,57 @@
+import type { StreamData } from "../../programs/venko";
+
+/**
+ * Descriptive information about a Stream.
+ */
+export interface StreamMetadataInfo {
+  /**
+   * A short name, at most 32 bytes when UTF-8 encoded.
+   */
+  name?: string;
+  /**
+   * A memo, at most 64 bytes when UTF-8 encoded.
+   */
+  memo?: string;
+  /**
+   * A 32-byte hash of an external reference, e.g. a contract or invoice.
+   */
+  externalRef?: Buffer;
+}
+
+const encodePadded = (data: Buffer, length: number): number[] => {
+  if (data.length > length) {
+    throw new Error(`metadata field exceeds ${length} bytes`);
+  }
+  return [...data, ...new Array<number>(length - data.length).fill(0)];
+};
+
+const decodePadded = (data: number[]): string =>
+  Buffer.from(data).toString("utf8").replace(/\0+$/, "");
+
+/**
+ * Encodes Stream metadata into its on-chain representation.
+ * @param metadata
+ * @returns
+ */
+export const encodeStreamMetadata = ({
+  name = "",
+  memo = "",
+  externalRef = Buffer.alloc(32),
+}: StreamMetadataInfo = {}): StreamData["metadata"] => ({
+  name: encodePadded(Buffer.from(name, "utf8"), 32),
+  memo: encodePadded(Buffer.from(memo, "utf8"), 64),
+  externalRef: encodePadded(externalRef, 32),
+});
+
+/**
+ * Decodes the on-chain metadata of a Stream.
+ * @param metadata
+ * @returns
+ */
+export const decodeStreamMetadata = (
+  metadata: StreamData["metadata"]
+): Required<StreamMetadataInfo> => ({
+  name: decodePadded(metadata.name),
+  memo: decodePadded(metadata.memo),
+  externalRef: Buffer.from(metadata.externalRef),
+});
@@ -1,5 +1,6 @@
 import { utils } from "@project-serum/anchor";
 import { PublicKey } from "@solana/web3.js";
+import type BN from "bn.js";
 
 import { VENKO_ADDRESSES } from "../../constants";
 
@@ -14,3 +15,90 @@ export const findStreamAddress = async (
     VENKO_ADDRESSES.Venko
   );
 };
+
+/**
+ * Finds the address of a Venko HolderClaim.
+ */
+export const findHolderClaimAddress = async (
+  stream: PublicKey,
+  owner: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("HolderClaim"),
+      stream.toBuffer(),
+      owner.toBuffer(),
+    ],
+    VENKO_ADDRESSES.Venko
+  );
+};
+
+/**
+ * Finds the address of a Venko VestingDistributor.
+ */
+export const findVestingDistributorAddress = async (
+  base: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [utils.bytes.utf8.encode("VestingDistributor"), base.toBuffer()],
+    VENKO_ADDRESSES.Venko
+  );
+};
+
+/**
+ * Finds the address of a Venko ClaimStatus.
+ */
+export const findClaimStatusAddress = async (
+  distributor: PublicKey,
+  claimant: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("ClaimStatus"),
+      distributor.toBuffer(),
+      claimant.toBuffer(),
+    ],
+    VENKO_ADDRESSES.Venko
+  );
+};
+
+/**
+ * Finds the address of the StreamIndex of the Streams created by an account.
+ */
+export const findCreatorIndexAddress = async (
+  creator: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [utils.bytes.utf8.encode("CreatorIndex"), creator.toBuffer()],
+    VENKO_ADDRESSES.Venko
+  );
+};
+
+/**
+ * Finds the address of the StreamIndex of the Streams issued to an account.
+ */
+export const findRecipientIndexAddress = async (
+  recipient: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [utils.bytes.utf8.encode("RecipientIndex"), recipient.toBuffer()],
+    VENKO_ADDRESSES.Venko
+  );
+};
+
+/**
+ * Finds the address of the StreamIndexEntry at a position of a StreamIndex.
+ */
+export const findStreamIndexEntryAddress = async (
+  index: PublicKey,
+  position: BN
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("StreamIndexEntry"),
+      index.toBuffer(),
+      position.toArrayLike(Buffer, "le", 8),
+    ],
+    VENKO_ADDRESSES.Venko
+  );
+};
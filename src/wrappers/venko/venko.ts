@@ -1,10 +1,12 @@
 import {
   CRATE_ADDRESSES,
+  CRATE_FEE_OWNER,
   generateCrateAddress,
 } from "@crateprotocol/crate-sdk";
 import type { TransactionEnvelope } from "@saberhq/solana-contrib";
 import type { TokenAmount } from "@saberhq/token-utils";
 import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
   createATAInstruction,
   createInitMintInstructions,
   getATAAddress,
@@ -14,14 +16,36 @@ import {
   Token,
   TOKEN_PROGRAM_ID,
 } from "@saberhq/token-utils";
-import type { Signer } from "@solana/web3.js";
-import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import type { Signer, TransactionInstruction } from "@solana/web3.js";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
 import BN from "bn.js";
 
 import { VENKO_CODERS } from "../..";
-import type { StreamData, VenkoProgram } from "../../programs/venko";
+import type {
+  StreamData,
+  StreamIndexData,
+  StreamIndexEntryData,
+  VenkoProgram,
+  VestingDistributorData,
+} from "../../programs/venko";
 import type { VenkoSDK } from "../../sdk";
-import { findStreamAddress } from "./pda";
+import type { StreamMetadataInfo } from "./metadata";
+import { decodeStreamV0, STREAM_LEN, STREAM_V0_LEN } from "./legacy";
+import { encodeStreamMetadata } from "./metadata";
+import {
+  findClaimStatusAddress,
+  findCreatorIndexAddress,
+  findHolderClaimAddress,
+  findRecipientIndexAddress,
+  findStreamAddress,
+  findStreamIndexEntryAddress,
+  findVestingDistributorAddress,
+} from "./pda";
 
 /**
  * Handles interacting with the Venko program.
@@ -42,12 +66,200 @@ export class VenkoWrapper {
   }
 
   /**
-   * Fetches a Stream.
+   * Fetches a Stream. Version 0 Streams are decoded as they will be after
+   * their migration.
    * @param key
    * @returns
    */
   async fetchStream(key: PublicKey): Promise<StreamData | null> {
-    return await this.program.account.stream.fetchNullable(key);
+    const info = await this.provider.connection.getAccountInfo(key);
+    if (!info) {
+      return null;
+    }
+    if (info.data.length === STREAM_V0_LEN) {
+      const defaults = this.program.coder.accounts.decode<StreamData>(
+        "Stream",
+        Buffer.concat([info.data.slice(0, 8), Buffer.alloc(STREAM_LEN - 8)])
+      );
+      return decodeStreamV0(info.data, defaults);
+    }
+    return this.program.coder.accounts.decode<StreamData>("Stream", info.data);
+  }
+
+  /**
+   * Creates the instruction migrating a Stream to the current layout, if it
+   * is a version 0 Stream.
+   * @returns
+   */
+  async getMigrateStreamInstruction(
+    stream: PublicKey,
+    payer = this.provider.wallet.publicKey
+  ): Promise<TransactionInstruction | null> {
+    const info = await this.provider.connection.getAccountInfo(stream);
+    if (info?.data.length !== STREAM_V0_LEN) {
+      return null;
+    }
+    return VENKO_CODERS.Venko.encodeIX(
+      "migrateStream",
+      {},
+      {
+        stream,
+        payer,
+        systemProgram: SystemProgram.programId,
+      }
+    );
+  }
+
+  /**
+   * Creates a transaction using a Stream, first migrating the Stream if it
+   * is a version 0 Stream.
+   * @returns
+   */
+  async newStreamTX(
+    stream: PublicKey,
+    instructions: (TransactionInstruction | null)[]
+  ): Promise<TransactionEnvelope> {
+    return this.provider.newTX([
+      await this.getMigrateStreamInstruction(stream),
+      ...instructions,
+    ]);
+  }
+
+  /**
+   * Fetches a StreamIndex.
+   * @param key
+   * @returns
+   */
+  async fetchStreamIndex(key: PublicKey): Promise<StreamIndexData | null> {
+    return await this.program.account.streamIndex.fetchNullable(key);
+  }
+
+  /**
+   * Fetches the Streams in a StreamIndex, e.g. the Streams created by an
+   * account.
+   * @param index
+   * @returns
+   */
+  async fetchIndexedStreams(index: PublicKey): Promise<PublicKey[]> {
+    const indexData = await this.fetchStreamIndex(index);
+    if (!indexData) {
+      return [];
+    }
+    const entries = await Promise.all(
+      Array.from({ length: indexData.count.toNumber() }, async (_, i) => {
+        const [entry] = await findStreamIndexEntryAddress(index, new BN(i));
+        return entry;
+      })
+    );
+    const entriesData =
+      await this.program.account.streamIndexEntry.fetchMultiple(entries);
+    return entriesData
+      .filter((entry): entry is StreamIndexEntryData => !!entry)
+      .map((entry) => entry.stream);
+  }
+
+  /**
+   * Finds the next StreamIndexEntry of a StreamIndex, offset by `skip`.
+   * @returns
+   */
+  async findNextIndexEntry(
+    index: PublicKey,
+    skip = 0
+  ): Promise<[PublicKey, number]> {
+    const indexData = await this.fetchStreamIndex(index);
+    const position = (indexData?.count ?? new BN(0)).add(new BN(skip));
+    return await findStreamIndexEntryAddress(index, position);
+  }
+
+  /**
+   * Finds the HolderClaim of an owner of Stream tokens, creating it if it
+   * does not exist.
+   * @returns
+   */
+  async getOrCreateHolderClaim({
+    stream,
+    owner,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    stream: PublicKey;
+    /**
+     * The owner of the Stream tokens.
+     */
+    owner: PublicKey;
+    payer?: PublicKey;
+  }): Promise<{
+    address: PublicKey;
+    instruction: TransactionInstruction | null;
+  }> {
+    const [holderClaim, bump] = await findHolderClaimAddress(stream, owner);
+    const holderClaimInfo = await this.provider.getAccountInfo(holderClaim);
+    if (holderClaimInfo) {
+      return { address: holderClaim, instruction: null };
+    }
+    return {
+      address: holderClaim,
+      instruction: VENKO_CODERS.Venko.encodeIX(
+        "createHolderClaim",
+        { bump },
+        {
+          stream,
+          owner,
+          holderClaim,
+          payer,
+          systemProgram: SystemProgram.programId,
+        }
+      ),
+    };
+  }
+
+  /**
+   * Finds the token accounts receiving the fees charged by the Crate of a
+   * Stream, creating them if they do not exist.
+   * @returns
+   */
+  async getOrCreateFeeDestinations({
+    streamData,
+    destination,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    streamData: StreamData;
+    /**
+     * Destination of the withdrawn tokens. This is also passed as the fee
+     * destinations if the Stream charges no fees.
+     */
+    destination: PublicKey;
+    payer?: PublicKey;
+  }): Promise<{
+    authorFeeDestination: PublicKey;
+    protocolFeeDestination: PublicKey;
+    instructions: TransactionInstruction[];
+  }> {
+    if (streamData.authorFeeTo.equals(PublicKey.default)) {
+      return {
+        authorFeeDestination: destination,
+        protocolFeeDestination: destination,
+        instructions: [],
+      };
+    }
+    const authorATA = await getOrCreateATA({
+      provider: this.provider,
+      mint: streamData.underlyingMint,
+      owner: streamData.authorFeeTo,
+      payer,
+    });
+    const protocolATA = await getOrCreateATA({
+      provider: this.provider,
+      mint: streamData.underlyingMint,
+      owner: CRATE_FEE_OWNER,
+      payer,
+    });
+    return {
+      authorFeeDestination: authorATA.address,
+      protocolFeeDestination: protocolATA.address,
+      instructions: [authorATA.instruction, protocolATA.instruction].filter(
+        (ix): ix is TransactionInstruction => !!ix
+      ),
+    };
   }
 
   /**
@@ -58,9 +270,19 @@ export class VenkoWrapper {
     amount,
     startTS,
     cliffTS = startTS,
+    cliffAmount,
     endTS,
+    period = 0,
+    breakpoints = [],
     mintKP = Keypair.generate(),
     revoker,
+    autoRedeem = false,
+    transferable = true,
+    cancelUntilTS = 0,
+    acceptanceDeadline = 0,
+    authorFeeTo,
+    withdrawFeeBps = 0,
+    metadata,
     owner = this.provider.wallet.publicKey,
     recipient = this.provider.wallet.publicKey,
     payer = this.provider.wallet.publicKey,
@@ -68,9 +290,58 @@ export class VenkoWrapper {
     amount: TokenAmount;
     startTS: number;
     cliffTS?: number;
+    /**
+     * Amount of tokens released in full at the cliff. Defaults to zero.
+     */
+    cliffAmount?: TokenAmount;
     endTS: number;
+    /**
+     * Length of each release period, in seconds.
+     * Defaults to continuous release.
+     */
+    period?: number;
+    /**
+     * Optional piecewise-linear release schedule, as a list of timestamps and
+     * cumulative released amounts. Replaces the linear release if provided.
+     */
+    breakpoints?: { ts: number; amount: TokenAmount }[];
     mintKP?: Signer;
     revoker?: PublicKey;
+    /**
+     * If true, anyone may redeem released tokens on behalf of holders who
+     * have approved the Stream as a delegate.
+     */
+    autoRedeem?: boolean;
+    /**
+     * If false, only the recipient may redeem the Stream tokens.
+     * Defaults to true.
+     */
+    transferable?: boolean;
+    /**
+     * Time until which the creator may cancel the Stream for a full refund.
+     * Tokens may not be redeemed before then. Must not be after `endTS`.
+     * Defaults to no cancellation.
+     */
+    cancelUntilTS?: number;
+    /**
+     * If provided, the Stream is pending until the recipient accepts it
+     * before this time, and `startTS`, `cliffTS`, `endTS`, `cancelUntilTS`
+     * and the breakpoints are relative to the time of acceptance.
+     */
+    acceptanceDeadline?: number;
+    /**
+     * Recipient of the fees charged on withdrawals. Required if
+     * `withdrawFeeBps` is non-zero.
+     */
+    authorFeeTo?: PublicKey;
+    /**
+     * Fee charged on withdrawals, in basis points. Defaults to zero.
+     */
+    withdrawFeeBps?: number;
+    /**
+     * Optional name, memo and external reference of the Stream.
+     */
+    metadata?: StreamMetadataInfo;
     /**
      * Owner of the underlying tokens to be streamed.
      */
@@ -92,8 +363,16 @@ export class VenkoWrapper {
     const [crateToken, crateBump] = await generateCrateAddress(
       mintKP.publicKey
     );
-    const ownerUnderlyingATA = await getOrCreateATA({
-      provider: this.provider,
+    const [creatorIndex, creatorIndexBump] = await findCreatorIndexAddress(
+      owner
+    );
+    const [creatorIndexEntry, creatorEntryBump] =
+      await this.findNextIndexEntry(creatorIndex);
+    const [recipientIndex, recipientIndexBump] =
+      await findRecipientIndexAddress(recipient);
+    const [recipientIndexEntry, recipientEntryBump] =
+      await this.findNextIndexEntry(recipientIndex);
+    const ownerUnderlyingATA = await getATAAddress({
       mint: amount.token.mintAccount,
       owner,
     });
@@ -106,18 +385,14 @@ export class VenkoWrapper {
       mint: amount.token.mintAccount,
       owner: crateToken,
     });
-    const underlyingTokensIX = createATAInstruction({
-      address: underlyingTokensATA,
-      mint: amount.token.mintAccount,
-      owner: crateToken,
-      payer,
-    });
     const endDate = new Date(endTS * 1_000);
+    const ends =
+      acceptanceDeadline === 0
+        ? `ends ${endDate.toLocaleString()}`
+        : "pending acceptance";
     const token = Token.fromMint(mintKP.publicKey, amount.token.decimals, {
       ...amount.token.info,
-      name: `Venko ${
-        amount.token.symbol
-      } Stream (ends ${endDate.toLocaleString()})`,
+      name: `Venko ${amount.token.symbol} Stream (${ends})`,
       symbol: `v${amount.token.symbol}`,
     });
 
@@ -130,43 +405,60 @@ export class VenkoWrapper {
           mintKP,
           decimals: amount.token.decimals,
           mintAuthority: crateToken,
-          freezeAuthority: crateToken,
+          // the Stream freezes the tokens of a non-transferable Stream
+          freezeAuthority: transferable ? crateToken : stream,
         })
       ).combine(
         this.provider.newTX(
           [
             recipientStreamATA.instruction,
-            ownerUnderlyingATA.instruction,
-            underlyingTokensIX,
-            SPLToken.createTransferInstruction(
-              TOKEN_PROGRAM_ID,
-              ownerUnderlyingATA.address,
-              underlyingTokensATA,
-              owner,
-              [],
-              amount.toU64()
-            ),
             VENKO_CODERS.Venko.encodeIX(
               "createStream",
               {
                 streamBump,
                 crateBump,
+                creatorIndexBump,
+                creatorEntryBump,
+                recipientIndexBump,
+                recipientEntryBump,
                 startTs: new BN(startTS),
                 cliffTs: new BN(cliffTS),
+                cliffAmount: cliffAmount?.toU64() ?? new BN(0),
                 endTs: new BN(endTS),
+                period: new BN(period),
+                breakpoints: breakpoints.map(({ ts, amount }) => ({
+                  ts: new BN(ts),
+                  amount: amount.toU64(),
+                })),
                 revoker: revoker ?? PublicKey.default,
+                autoRedeem,
+                transferable,
+                cancelUntilTs: new BN(cancelUntilTS),
+                acceptanceDeadline: new BN(acceptanceDeadline),
+                withdrawFeeBps,
+                amount: amount.toU64(),
+                metadata: encodeStreamMetadata(metadata),
               },
               {
                 streamMint: mintKP.publicKey,
                 stream,
                 underlyingMint: amount.token.mintAccount,
                 underlyingTokens: underlyingTokensATA,
+                sourceTokens: ownerUnderlyingATA,
+                sourceAuthority: owner,
                 destination: recipientStreamATA.address,
                 crateToken,
+                authorFeeTo: authorFeeTo ?? SystemProgram.programId,
+                creatorIndex,
+                creatorIndexEntry,
+                recipientIndex,
+                recipientIndexEntry,
                 payer,
                 systemProgram: SystemProgram.programId,
                 crateTokenProgram: CRATE_ADDRESSES.CrateToken,
                 tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                rent: SYSVAR_RENT_PUBKEY,
               }
             ),
           ],
@@ -176,6 +468,187 @@ export class VenkoWrapper {
     };
   }
 
+  /**
+   * Creates a Venko Stream for each of several recipients in a single
+   * transaction, sharing a release schedule.
+   *
+   * Every Stream in a batch is transferable, may not be cancelled, is active
+   * upon creation and charges no fees. Use {@link createStream} for Streams
+   * which need any other option.
+   * @returns
+   */
+  async createStreamBatch({
+    underlyingToken,
+    legs,
+    startTS,
+    cliffTS = startTS,
+    endTS,
+    period = 0,
+    revoker,
+    autoRedeem = false,
+    owner = this.provider.wallet.publicKey,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    underlyingToken: Token;
+    /**
+     * The recipients of the Streams and the amounts they receive.
+     */
+    legs: {
+      recipient: PublicKey;
+      amount: TokenAmount;
+      cliffAmount?: TokenAmount;
+      mintKP?: Signer;
+    }[];
+    startTS: number;
+    cliffTS?: number;
+    endTS: number;
+    period?: number;
+    revoker?: PublicKey;
+    autoRedeem?: boolean;
+    /**
+     * Owner of the underlying tokens to be streamed.
+     */
+    owner?: PublicKey;
+    payer?: PublicKey;
+  }): Promise<{
+    streams: { stream: PublicKey; token: Token }[];
+    tx: TransactionEnvelope;
+  }> {
+    const ownerUnderlyingATA = await getATAAddress({
+      mint: underlyingToken.mintAccount,
+      owner,
+    });
+    const [creatorIndex, creatorIndexBump] = await findCreatorIndexAddress(
+      owner
+    );
+    const endDate = new Date(endTS * 1_000);
+
+    const instructions: (TransactionInstruction | null)[] = [];
+    const signers: Signer[] = [];
+    const streams: { stream: PublicKey; token: Token }[] = [];
+    const legArgs: {
+      streamBump: number;
+      crateBump: number;
+      creatorEntryBump: number;
+      recipientIndexBump: number;
+      recipientEntryBump: number;
+      amount: BN;
+      cliffAmount: BN;
+    }[] = [];
+    const remainingAccounts: PublicKey[] = [];
+    // number of Streams in this batch issued to each recipient
+    const recipientCounts = new Map<string, number>();
+    for (const [
+      i,
+      { recipient, amount, cliffAmount, mintKP = Keypair.generate() },
+    ] of legs.entries()) {
+      const [stream, streamBump] = await findStreamAddress(mintKP.publicKey);
+      const [crateToken, crateBump] = await generateCrateAddress(
+        mintKP.publicKey
+      );
+      const [creatorIndexEntry, creatorEntryBump] =
+        await this.findNextIndexEntry(creatorIndex, i);
+      const [recipientIndex, recipientIndexBump] =
+        await findRecipientIndexAddress(recipient);
+      const recipientCount = recipientCounts.get(recipient.toString()) ?? 0;
+      recipientCounts.set(recipient.toString(), recipientCount + 1);
+      const [recipientIndexEntry, recipientEntryBump] =
+        await this.findNextIndexEntry(recipientIndex, recipientCount);
+      const recipientStreamATA = await getOrCreateATA({
+        provider: this.provider,
+        mint: mintKP.publicKey,
+        owner: recipient,
+        payer,
+      });
+      const underlyingTokensATA = await getATAAddress({
+        mint: underlyingToken.mintAccount,
+        owner: crateToken,
+      });
+      const initMintTX = await createInitMintInstructions({
+        provider: this.provider,
+        mintKP,
+        decimals: underlyingToken.decimals,
+        mintAuthority: crateToken,
+        freezeAuthority: crateToken,
+      });
+      instructions.push(
+        ...initMintTX.instructions,
+        recipientStreamATA.instruction,
+        createATAInstruction({
+          address: underlyingTokensATA,
+          mint: underlyingToken.mintAccount,
+          owner: crateToken,
+          payer,
+        })
+      );
+      signers.push(...initMintTX.signers);
+
+      streams.push({
+        stream,
+        token: Token.fromMint(mintKP.publicKey, underlyingToken.decimals, {
+          ...underlyingToken.info,
+          name: `Venko ${
+            underlyingToken.symbol
+          } Stream (ends ${endDate.toLocaleString()})`,
+          symbol: `v${underlyingToken.symbol}`,
+        }),
+      });
+      legArgs.push({
+        streamBump,
+        crateBump,
+        creatorEntryBump,
+        recipientIndexBump,
+        recipientEntryBump,
+        amount: amount.toU64(),
+        cliffAmount: cliffAmount?.toU64() ?? new BN(0),
+      });
+      remainingAccounts.push(
+        mintKP.publicKey,
+        stream,
+        crateToken,
+        underlyingTokensATA,
+        recipientStreamATA.address,
+        creatorIndexEntry,
+        recipientIndex,
+        recipientIndexEntry
+      );
+    }
+
+    const batchIX = VENKO_CODERS.Venko.encodeIX(
+      "createStreamBatch",
+      {
+        creatorIndexBump,
+        startTs: new BN(startTS),
+        cliffTs: new BN(cliffTS),
+        endTs: new BN(endTS),
+        period: new BN(period),
+        revoker: revoker ?? PublicKey.default,
+        autoRedeem,
+        legs: legArgs,
+      },
+      {
+        underlyingMint: underlyingToken.mintAccount,
+        sourceTokens: ownerUnderlyingATA,
+        sourceAuthority: owner,
+        creatorIndex,
+        payer,
+        systemProgram: SystemProgram.programId,
+        crateTokenProgram: CRATE_ADDRESSES.CrateToken,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      }
+    );
+    batchIX.keys.push(
+      ...remainingAccounts.map((pubkey) => ({
+        pubkey,
+        isSigner: false,
+        isWritable: true,
+      }))
+    );
+    instructions.push(batchIX);
+
+    return { streams, tx: this.provider.newTX(instructions, signers) };
+  }
+
   /**
    * Redeems Stream tokens.
    * @returns
@@ -183,6 +656,7 @@ export class VenkoWrapper {
   async redeem({
     amount,
     owner = this.provider.wallet.publicKey,
+    authority = owner,
     recipient = this.provider.wallet.publicKey,
   }: {
     /**
@@ -193,6 +667,10 @@ export class VenkoWrapper {
      * Owner of the Stream tokens.
      */
     owner?: PublicKey;
+    /**
+     * Owner or approved delegate of the Stream tokens. Defaults to the owner.
+     */
+    authority?: PublicKey;
     /**
      * Recipient of the redeemed tokens.
      */
@@ -207,12 +685,24 @@ export class VenkoWrapper {
       mint: amount.token.mintAccount,
       owner,
     });
+    const holderClaim = await this.getOrCreateHolderClaim({
+      stream,
+      owner,
+      payer: authority,
+    });
     const recipientUnderlyingATA = await getOrCreateATA({
       provider: this.provider,
       mint: streamData.underlyingMint,
       owner: recipient,
+      payer: authority,
     });
-    return this.provider.newTX([
+    const feeDestinations = await this.getOrCreateFeeDestinations({
+      streamData,
+      destination: recipientUnderlyingATA.address,
+    });
+    return await this.newStreamTX(stream, [
+      ...feeDestinations.instructions,
+      holderClaim.instruction,
       recipientUnderlyingATA.instruction,
       VENKO_CODERS.Venko.encodeIX(
         "redeem",
@@ -223,10 +713,13 @@ export class VenkoWrapper {
           streamMint: amount.token.mintAccount,
           stream,
           sourceStreamTokens: ownerStreamATA,
+          holderClaim: holderClaim.address,
           underlyingTokens: streamData.underlyingTokens,
           destinationTokens: recipientUnderlyingATA.address,
+          authorFeeDestination: feeDestinations.authorFeeDestination,
+          protocolFeeDestination: feeDestinations.protocolFeeDestination,
           crateToken: streamData.crateToken,
-          userAuthority: owner,
+          userAuthority: authority,
           systemProgram: SystemProgram.programId,
           crateTokenProgram: CRATE_ADDRESSES.CrateToken,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -236,51 +729,910 @@ export class VenkoWrapper {
   }
 
   /**
-   * Revokes a Stream.
+   * Redeems all Stream tokens which are available at execution time.
    * @returns
    */
-  async revoke({
+  async redeemMax({
     streamMint,
     owner = this.provider.wallet.publicKey,
-    revoker = this.provider.wallet.publicKey,
+    recipient = this.provider.wallet.publicKey,
   }: {
     /**
-     * The mint of the Stream to revoke.
+     * The mint of the Stream.
      */
     streamMint: PublicKey;
     /**
-     * Owner to send the tokens to.
+     * Owner of the Stream tokens.
      */
     owner?: PublicKey;
-    revoker?: PublicKey;
+    /**
+     * Recipient of the redeemed tokens.
+     */
+    recipient?: PublicKey;
   }): Promise<TransactionEnvelope> {
     const [stream] = await findStreamAddress(streamMint);
     const streamData = await this.fetchStream(stream);
     if (!streamData) {
       throw new Error(`stream not found: ${stream.toString()}`);
     }
-    const ownerATAs = await getOrCreateATAs({
-      provider: this.provider,
-      mints: {
-        underlying: streamData.underlyingMint,
-      },
+    const ownerStreamATA = await getATAAddress({
+      mint: streamMint,
       owner,
     });
-    return this.provider.newTX([
-      ...ownerATAs.instructions,
+    const holderClaim = await this.getOrCreateHolderClaim({
+      stream,
+      owner,
+    });
+    const recipientUnderlyingATA = await getOrCreateATA({
+      provider: this.provider,
+      mint: streamData.underlyingMint,
+      owner: recipient,
+    });
+    const feeDestinations = await this.getOrCreateFeeDestinations({
+      streamData,
+      destination: recipientUnderlyingATA.address,
+    });
+    return await this.newStreamTX(stream, [
+      ...feeDestinations.instructions,
+      holderClaim.instruction,
+      recipientUnderlyingATA.instruction,
       VENKO_CODERS.Venko.encodeIX(
-        "revoke",
+        "redeemMax",
         {},
         {
+          streamMint,
           stream,
+          sourceStreamTokens: ownerStreamATA,
+          holderClaim: holderClaim.address,
+          underlyingTokens: streamData.underlyingTokens,
+          destinationTokens: recipientUnderlyingATA.address,
+          authorFeeDestination: feeDestinations.authorFeeDestination,
+          protocolFeeDestination: feeDestinations.protocolFeeDestination,
           crateToken: streamData.crateToken,
+          userAuthority: owner,
+          systemProgram: SystemProgram.programId,
+          crateTokenProgram: CRATE_ADDRESSES.CrateToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Redeems all released tokens on behalf of the holder of an auto-redeem
+   * Stream, sending them to the holder.
+   * @returns
+   */
+  async crankRedeem({
+    streamMint,
+    holder,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * The mint of the Stream.
+     */
+    streamMint: PublicKey;
+    /**
+     * Owner of the Stream tokens, who has approved the Stream as a delegate.
+     */
+    holder: PublicKey;
+    payer?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [stream] = await findStreamAddress(streamMint);
+    const streamData = await this.fetchStream(stream);
+    if (!streamData) {
+      throw new Error(`stream not found: ${stream.toString()}`);
+    }
+    const holderStreamATA = await getATAAddress({
+      mint: streamMint,
+      owner: holder,
+    });
+    const holderClaim = await this.getOrCreateHolderClaim({
+      stream,
+      owner: holder,
+      payer,
+    });
+    const holderUnderlyingATA = await getOrCreateATA({
+      provider: this.provider,
+      mint: streamData.underlyingMint,
+      owner: holder,
+      payer,
+    });
+    const feeDestinations = await this.getOrCreateFeeDestinations({
+      streamData,
+      destination: holderUnderlyingATA.address,
+    });
+    return await this.newStreamTX(stream, [
+      ...feeDestinations.instructions,
+      holderClaim.instruction,
+      holderUnderlyingATA.instruction,
+      VENKO_CODERS.Venko.encodeIX(
+        "crankRedeem",
+        {},
+        {
+          streamMint,
+          stream,
+          sourceStreamTokens: holderStreamATA,
+          holderClaim: holderClaim.address,
           underlyingTokens: streamData.underlyingTokens,
-          destinationTokens: ownerATAs.accounts.underlying,
-          revoker,
+          destinationTokens: holderUnderlyingATA.address,
+          authorFeeDestination: feeDestinations.authorFeeDestination,
+          protocolFeeDestination: feeDestinations.protocolFeeDestination,
+          crateToken: streamData.crateToken,
           crateTokenProgram: CRATE_ADDRESSES.CrateToken,
           tokenProgram: TOKEN_PROGRAM_ID,
         }
       ),
     ]);
   }
+
+  /**
+   * Adds more underlying tokens to a Stream.
+   * @returns
+   */
+  async topUp({
+    streamMint,
+    amount,
+    funder = this.provider.wallet.publicKey,
+    recipient = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * The mint of the Stream to top up.
+     */
+    streamMint: PublicKey;
+    /**
+     * Amount of underlying tokens to add.
+     */
+    amount: TokenAmount;
+    /**
+     * Owner of the underlying tokens to add.
+     */
+    funder?: PublicKey;
+    /**
+     * Recipient of the new Stream tokens.
+     */
+    recipient?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [stream] = await findStreamAddress(streamMint);
+    const streamData = await this.fetchStream(stream);
+    if (!streamData) {
+      throw new Error(`stream not found: ${stream.toString()}`);
+    }
+    const funderUnderlyingATA = await getATAAddress({
+      mint: streamData.underlyingMint,
+      owner: funder,
+    });
+    const recipientStreamATA = await getOrCreateATA({
+      provider: this.provider,
+      mint: streamMint,
+      owner: recipient,
+    });
+    return await this.newStreamTX(stream, [
+      recipientStreamATA.instruction,
+      VENKO_CODERS.Venko.encodeIX(
+        "topUp",
+        {
+          amount: amount.toU64(),
+        },
+        {
+          streamMint,
+          stream,
+          crateToken: streamData.crateToken,
+          underlyingTokens: streamData.underlyingTokens,
+          sourceTokens: funderUnderlyingATA,
+          sourceAuthority: funder,
+          destination: recipientStreamATA.address,
+          crateTokenProgram: CRATE_ADDRESSES.CrateToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Revokes a Stream.
+   * @returns
+   */
+  async revoke({
+    streamMint,
+    unvestedOnly = false,
+    owner = this.provider.wallet.publicKey,
+    revoker = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * The mint of the Stream to revoke.
+     */
+    streamMint: PublicKey;
+    /**
+     * If true, only revokes the unvested tokens, leaving vested tokens
+     * redeemable by the Stream token holders.
+     */
+    unvestedOnly?: boolean;
+    /**
+     * Owner to send the tokens to.
+     */
+    owner?: PublicKey;
+    revoker?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [stream] = await findStreamAddress(streamMint);
+    const streamData = await this.fetchStream(stream);
+    if (!streamData) {
+      throw new Error(`stream not found: ${stream.toString()}`);
+    }
+    const ownerATAs = await getOrCreateATAs({
+      provider: this.provider,
+      mints: {
+        underlying: streamData.underlyingMint,
+      },
+      owner,
+    });
+    return await this.newStreamTX(stream, [
+      ...ownerATAs.instructions,
+      VENKO_CODERS.Venko.encodeIX(
+        unvestedOnly ? "revokeUnvested" : "revoke",
+        {},
+        {
+          stream,
+          crateToken: streamData.crateToken,
+          underlyingTokens: streamData.underlyingTokens,
+          destinationTokens: ownerATAs.accounts.underlying,
+          revoker,
+          crateTokenProgram: CRATE_ADDRESSES.CrateToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Schedules the revocation of a Stream.
+   * @returns
+   */
+  async scheduleRevocation({
+    streamMint,
+    revokeTS,
+    revoker = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * The mint of the Stream to revoke.
+     */
+    streamMint: PublicKey;
+    /**
+     * Time at which the Stream should be revoked.
+     */
+    revokeTS: number;
+    revoker?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [stream] = await findStreamAddress(streamMint);
+    return await this.newStreamTX(stream, [
+      VENKO_CODERS.Venko.encodeIX(
+        "scheduleRevocation",
+        { revokeTs: new BN(revokeTS) },
+        { stream, revoker }
+      ),
+    ]);
+  }
+
+  /**
+   * Cancels the scheduled revocation of a Stream.
+   * @returns
+   */
+  async cancelRevocation({
+    streamMint,
+    revoker = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * The mint of the Stream.
+     */
+    streamMint: PublicKey;
+    revoker?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [stream] = await findStreamAddress(streamMint);
+    return await this.newStreamTX(stream, [
+      VENKO_CODERS.Venko.encodeIX("cancelRevocation", {}, { stream, revoker }),
+    ]);
+  }
+
+  /**
+   * Executes the scheduled revocation of a Stream once it has passed.
+   * @returns
+   */
+  async executeRevocation({
+    streamMint,
+  }: {
+    /**
+     * The mint of the Stream.
+     */
+    streamMint: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [stream] = await findStreamAddress(streamMint);
+    const streamData = await this.fetchStream(stream);
+    if (!streamData) {
+      throw new Error(`stream not found: ${stream.toString()}`);
+    }
+    const revokerATA = await getOrCreateATA({
+      provider: this.provider,
+      mint: streamData.underlyingMint,
+      owner: streamData.revoker,
+    });
+    return await this.newStreamTX(stream, [
+      revokerATA.instruction,
+      VENKO_CODERS.Venko.encodeIX(
+        "executeRevocation",
+        {},
+        {
+          stream,
+          crateToken: streamData.crateToken,
+          underlyingTokens: streamData.underlyingTokens,
+          destinationTokens: revokerATA.address,
+          crateTokenProgram: CRATE_ADDRESSES.CrateToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Nominates a new revoker of a Stream.
+   * @returns
+   */
+  async transferRevoker({
+    streamMint,
+    newRevoker,
+    revoker = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * The mint of the Stream.
+     */
+    streamMint: PublicKey;
+    /**
+     * The nominated revoker, who must accept the role.
+     */
+    newRevoker: PublicKey;
+    revoker?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [stream] = await findStreamAddress(streamMint);
+    return await this.newStreamTX(stream, [
+      VENKO_CODERS.Venko.encodeIX(
+        "transferRevoker",
+        { newRevoker },
+        { stream, revoker }
+      ),
+    ]);
+  }
+
+  /**
+   * Accepts the revoker role of a Stream.
+   * @returns
+   */
+  async acceptRevoker({
+    streamMint,
+    pendingRevoker = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * The mint of the Stream.
+     */
+    streamMint: PublicKey;
+    pendingRevoker?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [stream] = await findStreamAddress(streamMint);
+    return await this.newStreamTX(stream, [
+      VENKO_CODERS.Venko.encodeIX(
+        "acceptRevoker",
+        {},
+        { stream, pendingRevoker }
+      ),
+    ]);
+  }
+
+  /**
+   * Renounces the revoker role of a Stream, making it irrevocable.
+   * @returns
+   */
+  async renounceRevoker({
+    streamMint,
+    revoker = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * The mint of the Stream.
+     */
+    streamMint: PublicKey;
+    revoker?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [stream] = await findStreamAddress(streamMint);
+    return await this.newStreamTX(stream, [
+      VENKO_CODERS.Venko.encodeIX("renounceRevoker", {}, { stream, revoker }),
+    ]);
+  }
+
+  /**
+   * Pauses a Stream.
+   * @returns
+   */
+  async pause({
+    streamMint,
+    revoker = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * The mint of the Stream.
+     */
+    streamMint: PublicKey;
+    revoker?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [stream] = await findStreamAddress(streamMint);
+    return await this.newStreamTX(stream, [
+      VENKO_CODERS.Venko.encodeIX("pause", {}, { stream, revoker }),
+    ]);
+  }
+
+  /**
+   * Resumes a paused Stream.
+   * @returns
+   */
+  async resume({
+    streamMint,
+    revoker = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * The mint of the Stream.
+     */
+    streamMint: PublicKey;
+    revoker?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [stream] = await findStreamAddress(streamMint);
+    return await this.newStreamTX(stream, [
+      VENKO_CODERS.Venko.encodeIX("resume", {}, { stream, revoker }),
+    ]);
+  }
+
+  /**
+   * Updates the metadata of a Stream.
+   * @returns
+   */
+  async updateStreamMetadata({
+    streamMint,
+    metadata,
+    authority = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * The mint of the Stream.
+     */
+    streamMint: PublicKey;
+    metadata: StreamMetadataInfo;
+    /**
+     * The creator or revoker of the Stream.
+     */
+    authority?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [stream] = await findStreamAddress(streamMint);
+    return await this.newStreamTX(stream, [
+      VENKO_CODERS.Venko.encodeIX(
+        "updateStreamMetadata",
+        { metadata: encodeStreamMetadata(metadata) },
+        { stream, authority }
+      ),
+    ]);
+  }
+
+  /**
+   * Cancels a Stream before its cancellation deadline, refunding all of its
+   * underlying tokens.
+   * @returns
+   */
+  async cancelStream({
+    streamMint,
+    owner = this.provider.wallet.publicKey,
+    creator = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * The mint of the Stream to cancel.
+     */
+    streamMint: PublicKey;
+    /**
+     * Owner to send the tokens to.
+     */
+    owner?: PublicKey;
+    creator?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [stream] = await findStreamAddress(streamMint);
+    const streamData = await this.fetchStream(stream);
+    if (!streamData) {
+      throw new Error(`stream not found: ${stream.toString()}`);
+    }
+    const ownerATAs = await getOrCreateATAs({
+      provider: this.provider,
+      mints: {
+        underlying: streamData.underlyingMint,
+      },
+      owner,
+    });
+    return await this.newStreamTX(stream, [
+      ...ownerATAs.instructions,
+      VENKO_CODERS.Venko.encodeIX(
+        "cancelStream",
+        {},
+        {
+          stream,
+          crateToken: streamData.crateToken,
+          underlyingTokens: streamData.underlyingTokens,
+          destinationTokens: ownerATAs.accounts.underlying,
+          creator,
+          crateTokenProgram: CRATE_ADDRESSES.CrateToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Accepts a pending Stream, starting its schedule.
+   * @returns
+   */
+  async acceptStream({
+    streamMint,
+    recipient = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * The mint of the Stream to accept.
+     */
+    streamMint: PublicKey;
+    /**
+     * Holder of the entire supply of Stream tokens.
+     */
+    recipient?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [stream] = await findStreamAddress(streamMint);
+    const recipientStreamATA = await getATAAddress({
+      mint: streamMint,
+      owner: recipient,
+    });
+    return await this.newStreamTX(stream, [
+      VENKO_CODERS.Venko.encodeIX(
+        "acceptStream",
+        {},
+        {
+          streamMint,
+          stream,
+          recipientStreamTokens: recipientStreamATA,
+          recipient,
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Rejects a Stream, burning all of its tokens and returning the underlying
+   * tokens to its creator.
+   * @returns
+   */
+  async rejectStream({
+    streamMint,
+    holder = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * The mint of the Stream to reject.
+     */
+    streamMint: PublicKey;
+    /**
+     * Holder of the entire supply of Stream tokens.
+     */
+    holder?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [stream] = await findStreamAddress(streamMint);
+    const streamData = await this.fetchStream(stream);
+    if (!streamData) {
+      throw new Error(`stream not found: ${stream.toString()}`);
+    }
+    const holderStreamATA = await getATAAddress({
+      mint: streamMint,
+      owner: holder,
+    });
+    const creatorUnderlyingATA = await getOrCreateATA({
+      provider: this.provider,
+      mint: streamData.underlyingMint,
+      owner: streamData.creator,
+      payer: holder,
+    });
+    return await this.newStreamTX(stream, [
+      creatorUnderlyingATA.instruction,
+      VENKO_CODERS.Venko.encodeIX(
+        "rejectStream",
+        {},
+        {
+          streamMint,
+          stream,
+          sourceStreamTokens: holderStreamATA,
+          holder,
+          crateToken: streamData.crateToken,
+          underlyingTokens: streamData.underlyingTokens,
+          destinationTokens: creatorUnderlyingATA.address,
+          crateTokenProgram: CRATE_ADDRESSES.CrateToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Migrates a version 0 Stream account to the current layout.
+   * @returns
+   */
+  async migrateStream({
+    streamMint,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * The mint of the Stream.
+     */
+    streamMint: PublicKey;
+    /**
+     * Payer for the rent of the additional space.
+     */
+    payer?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [stream] = await findStreamAddress(streamMint);
+    return this.provider.newTX([
+      VENKO_CODERS.Venko.encodeIX(
+        "migrateStream",
+        {},
+        {
+          stream,
+          payer,
+          systemProgram: SystemProgram.programId,
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Closes a drained Stream, reclaiming its rent.
+   * @returns
+   */
+  async closeStream({
+    streamMint,
+    creator = this.provider.wallet.publicKey,
+    recipient = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * The mint of the Stream.
+     */
+    streamMint: PublicKey;
+    /**
+     * The creator of the Stream.
+     */
+    creator?: PublicKey;
+    /**
+     * Recipient of the rent.
+     */
+    recipient?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [stream] = await findStreamAddress(streamMint);
+    const streamData = await this.fetchStream(stream);
+    if (!streamData) {
+      throw new Error(`stream not found: ${stream.toString()}`);
+    }
+    return await this.newStreamTX(stream, [
+      VENKO_CODERS.Venko.encodeIX(
+        "closeStream",
+        {},
+        {
+          stream,
+          streamMint,
+          underlyingTokens: streamData.underlyingTokens,
+          creator,
+          recipient,
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Thaws the Stream tokens of a finished non-transferable Stream, so that
+   * they may be burned.
+   * @returns
+   */
+  async thawStreamTokens({
+    streamMint,
+    owner = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * The mint of the Stream.
+     */
+    streamMint: PublicKey;
+    /**
+     * Owner of the frozen Stream tokens.
+     */
+    owner?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [stream] = await findStreamAddress(streamMint);
+    const streamData = await this.fetchStream(stream);
+    if (!streamData) {
+      throw new Error(`stream not found: ${stream.toString()}`);
+    }
+    const streamTokens = await getATAAddress({
+      mint: streamMint,
+      owner,
+    });
+    return await this.newStreamTX(stream, [
+      VENKO_CODERS.Venko.encodeIX(
+        "thawStreamTokens",
+        {},
+        {
+          stream,
+          streamMint,
+          underlyingTokens: streamData.underlyingTokens,
+          streamTokens,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Fetches a VestingDistributor.
+   * @param key
+   * @returns
+   */
+  async fetchVestingDistributor(
+    key: PublicKey
+  ): Promise<VestingDistributorData | null> {
+    return await this.program.account.vestingDistributor.fetchNullable(key);
+  }
+
+  /**
+   * Creates a VestingDistributor, funding it with the full amount of the
+   * allocations.
+   * @returns
+   */
+  async newVestingDistributor({
+    root,
+    maxTotalClaim,
+    startTS,
+    cliffTS = startTS,
+    endTS,
+    period = 0,
+    baseKP = Keypair.generate(),
+    owner = this.provider.wallet.publicKey,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Root of the Merkle tree of (claimant, allocation) entries.
+     */
+    root: Buffer;
+    /**
+     * The sum of all allocations.
+     */
+    maxTotalClaim: TokenAmount;
+    startTS: number;
+    cliffTS?: number;
+    endTS: number;
+    period?: number;
+    baseKP?: Signer;
+    /**
+     * Owner of the underlying tokens to be distributed.
+     */
+    owner?: PublicKey;
+    payer?: PublicKey;
+  }): Promise<{
+    distributor: PublicKey;
+    tx: TransactionEnvelope;
+  }> {
+    const [distributor, bump] = await findVestingDistributorAddress(
+      baseKP.publicKey
+    );
+    const ownerUnderlyingATA = await getATAAddress({
+      mint: maxTotalClaim.token.mintAccount,
+      owner,
+    });
+    const underlyingTokens = await getOrCreateATA({
+      provider: this.provider,
+      mint: maxTotalClaim.token.mintAccount,
+      owner: distributor,
+      payer,
+    });
+    return {
+      distributor,
+      tx: this.provider.newTX(
+        [
+          underlyingTokens.instruction,
+          VENKO_CODERS.Venko.encodeIX(
+            "newVestingDistributor",
+            {
+              bump,
+              root: [...root],
+              maxTotalClaim: maxTotalClaim.toU64(),
+              startTs: new BN(startTS),
+              cliffTs: new BN(cliffTS),
+              endTs: new BN(endTS),
+              period: new BN(period),
+            },
+            {
+              base: baseKP.publicKey,
+              distributor,
+              underlyingMint: maxTotalClaim.token.mintAccount,
+              underlyingTokens: underlyingTokens.address,
+              payer,
+              systemProgram: SystemProgram.programId,
+            }
+          ),
+          SPLToken.createTransferInstruction(
+            TOKEN_PROGRAM_ID,
+            ownerUnderlyingATA,
+            underlyingTokens.address,
+            owner,
+            [],
+            maxTotalClaim.toU64()
+          ),
+        ],
+        [baseKP]
+      ),
+    };
+  }
+
+  /**
+   * Claims all released tokens of a claimant's allocation from a
+   * VestingDistributor, proving the allocation if it has not yet been proven.
+   * @returns
+   */
+  async claimVested({
+    distributor,
+    allocation,
+    proof,
+    claimant = this.provider.wallet.publicKey,
+  }: {
+    distributor: PublicKey;
+    /**
+     * The claimant's allocation, as committed to by the Merkle root.
+     */
+    allocation: BN;
+    /**
+     * Merkle proof of the claimant's allocation.
+     */
+    proof: Buffer[];
+    claimant?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const distributorData = await this.fetchVestingDistributor(distributor);
+    if (!distributorData) {
+      throw new Error(`distributor not found: ${distributor.toString()}`);
+    }
+    const [claimStatus, bump] = await findClaimStatusAddress(
+      distributor,
+      claimant
+    );
+    const claimStatusInfo = await this.provider.getAccountInfo(claimStatus);
+    const claimantATA = await getOrCreateATA({
+      provider: this.provider,
+      mint: distributorData.underlyingMint,
+      owner: claimant,
+      payer: claimant,
+    });
+    return this.provider.newTX([
+      claimStatusInfo
+        ? null
+        : VENKO_CODERS.Venko.encodeIX(
+            "createClaimStatus",
+            { bump, allocation, proof: proof.map((node) => [...node]) },
+            {
+              distributor,
+              claimant,
+              claimStatus,
+              payer: claimant,
+              systemProgram: SystemProgram.programId,
+            }
+          ),
+      claimantATA.instruction,
+      VENKO_CODERS.Venko.encodeIX(
+        "claimVested",
+        {},
+        {
+          distributor,
+          claimStatus,
+          underlyingTokens: distributorData.underlyingTokens,
+          destinationTokens: claimantATA.address,
+          claimant,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        }
+      ),
+    ]);
+  }
 }
@@ -1,6 +1,7 @@
-import { expectTXTable } from "@saberhq/chai-solana";
+import { expectTX, expectTXTable } from "@saberhq/chai-solana";
 import {
   createMint,
+  getATAAddress,
   getOrCreateATA,
   sleep,
   SPLToken,
@@ -8,11 +9,30 @@ import {
   TOKEN_PROGRAM_ID,
   TokenAmount,
 } from "@saberhq/token-utils";
-import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import { expect } from "chai";
+import { createHash } from "crypto";
 
-import { VenkoSDK } from "../src";
+import {
+  decodeStreamMetadata,
+  findCreatorIndexAddress,
+  findRecipientIndexAddress,
+  findStreamAddress,
+  VENKO_CODERS,
+  VenkoSDK,
+} from "../src";
 import { makeSDK } from "./workspace/workspace";
 
+const sha256 = (...data: Buffer[]): Buffer =>
+  createHash("sha256").update(Buffer.concat(data)).digest();
+
+const vestingLeaf = (claimant: PublicKey, allocation: BN): Buffer =>
+  sha256(claimant.toBuffer(), allocation.toArrayLike(Buffer, "le", 8));
+
+const hashPair = (a: Buffer, b: Buffer): Buffer =>
+  Buffer.compare(a, b) <= 0 ? sha256(a, b) : sha256(b, a);
+
 describe("Venko", () => {
   const { provider } = makeSDK();
 
@@ -26,6 +46,11 @@ describe("Venko", () => {
     provider: provider.withSigner(recipientKP),
   });
 
+  const crankerKP = Keypair.generate();
+  const crankerSDK = VenkoSDK.load({
+    provider: provider.withSigner(crankerKP),
+  });
+
   before(async () => {
     await (
       await adminSDK.provider.requestAirdrop(LAMPORTS_PER_SOL * 10)
@@ -33,9 +58,20 @@ describe("Venko", () => {
     await (
       await recipientSDK.provider.requestAirdrop(LAMPORTS_PER_SOL * 10)
     ).wait();
+    await (
+      await crankerSDK.provider.requestAirdrop(LAMPORTS_PER_SOL * 10)
+    ).wait();
   });
 
-  it("should allow creating an irrevocable stream", async () => {
+  /**
+   * Creates an underlying token and mints 10 of it to the admin.
+   */
+  const setupUnderlying = async (): Promise<{
+    underlyingToken: Token;
+    amount: TokenAmount;
+    adminUnderlyingTokens: PublicKey;
+    nowTS: number;
+  }> => {
     const underlyingToken = Token.fromMint(
       await createMint(adminSDK.provider, undefined, 6),
       6
@@ -62,7 +98,16 @@ describe("Venko", () => {
       ])
     ).to.be.fulfilled;
 
-    const nowTS = Math.floor(new Date().getTime() / 1_000);
+    return {
+      underlyingToken,
+      amount,
+      adminUnderlyingTokens: adminUnderlyingTokens.address,
+      nowTS: Math.floor(new Date().getTime() / 1_000),
+    };
+  };
+
+  it("should allow creating an irrevocable stream", async () => {
+    const { amount, nowTS } = await setupUnderlying();
 
     const { tx, token: streamToken } = await adminSDK.venko.createStream({
       amount,
@@ -86,53 +131,1215 @@ describe("Venko", () => {
   });
 
   it("should allow creating a revocable stream", async () => {
-    const underlyingToken = Token.fromMint(
-      await createMint(adminSDK.provider, undefined, 6),
-      6
-    );
-    const amount = TokenAmount.parse(underlyingToken, "10");
+    const { amount, nowTS } = await setupUnderlying();
 
-    const adminUnderlyingTokens = await getOrCreateATA({
-      provider: adminSDK.provider,
-      owner: adminSDK.provider.wallet.publicKey,
+    const { tx, token: streamToken } = await adminSDK.venko.createStream({
+      amount,
+      startTS: nowTS,
+      endTS: nowTS + 3,
+      recipient: recipientKP.publicKey,
+      revoker: adminSDK.provider.wallet.publicKey,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    // wait for stream to be over...
+    await sleep(5_000);
+
+    const claimTX = await recipientSDK.venko.redeem({
+      amount: TokenAmount.parse(streamToken, "1"),
+    });
+    await expectTXTable(claimTX, "redeem stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+  });
+
+  it("should allow redeeming via a delegate", async () => {
+    const { underlyingToken, amount, nowTS } = await setupUnderlying();
+
+    const { tx, token: streamToken } = await adminSDK.venko.createStream({
+      amount,
+      startTS: nowTS,
+      endTS: nowTS + 3,
+      recipient: recipientKP.publicKey,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    const recipientStreamATA = await getATAAddress({
+      mint: streamToken.mintAccount,
+      owner: recipientKP.publicKey,
+    });
+    const redeemAmount = TokenAmount.parse(streamToken, "1");
+    await expectTX(
+      recipientSDK.provider.newTX([
+        SPLToken.createApproveInstruction(
+          TOKEN_PROGRAM_ID,
+          recipientStreamATA,
+          crankerKP.publicKey,
+          recipientKP.publicKey,
+          [],
+          redeemAmount.toU64()
+        ),
+      ]),
+      "approve delegate"
+    ).to.be.fulfilled;
+
+    // wait for stream to be over...
+    await sleep(5_000);
+
+    const claimTX = await crankerSDK.venko.redeem({
+      amount: redeemAmount,
+      owner: recipientKP.publicKey,
+      authority: crankerKP.publicKey,
+      recipient: recipientKP.publicKey,
+    });
+    await expectTXTable(claimTX, "redeem stream via delegate", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    const recipientUnderlyingATA = await getATAAddress({
       mint: underlyingToken.mintAccount,
+      owner: recipientKP.publicKey,
     });
+    const recipientUnderlying = await provider.connection.getTokenAccountBalance(
+      recipientUnderlyingATA
+    );
+    expect(recipientUnderlying.value.amount).to.eq(
+      redeemAmount.toU64().toString()
+    );
+  });
 
-    await expectTXTable(
-      adminSDK.provider.newTX([
-        adminUnderlyingTokens.instruction,
-        SPLToken.createMintToInstruction(
+  it("should allow cranking redemptions of an auto-redeem stream", async () => {
+    const { underlyingToken, amount, nowTS } = await setupUnderlying();
+
+    const { tx, token: streamToken } = await adminSDK.venko.createStream({
+      amount,
+      startTS: nowTS,
+      endTS: nowTS + 3,
+      recipient: recipientKP.publicKey,
+      autoRedeem: true,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    const [stream] = await findStreamAddress(streamToken.mintAccount);
+    const recipientStreamATA = await getATAAddress({
+      mint: streamToken.mintAccount,
+      owner: recipientKP.publicKey,
+    });
+    await expectTX(
+      recipientSDK.provider.newTX([
+        SPLToken.createApproveInstruction(
           TOKEN_PROGRAM_ID,
-          underlyingToken.mintAccount,
-          adminUnderlyingTokens.address,
-          adminSDK.provider.wallet.publicKey,
+          recipientStreamATA,
+          stream,
+          recipientKP.publicKey,
           [],
           amount.toU64()
         ),
-      ])
+      ]),
+      "approve stream as delegate"
     ).to.be.fulfilled;
 
-    const nowTS = Math.floor(new Date().getTime() / 1_000);
+    // wait for stream to be over...
+    await sleep(5_000);
 
-    const { tx, token: streamToken } = await adminSDK.venko.createStream({
+    const crankTX = await crankerSDK.venko.crankRedeem({
+      streamMint: streamToken.mintAccount,
+      holder: recipientKP.publicKey,
+    });
+    await expectTXTable(crankTX, "crank redeem stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    const recipientUnderlyingATA = await getATAAddress({
+      mint: underlyingToken.mintAccount,
+      owner: recipientKP.publicKey,
+    });
+    const recipientUnderlying = await provider.connection.getTokenAccountBalance(
+      recipientUnderlyingATA
+    );
+    expect(recipientUnderlying.value.amount).to.eq(amount.toU64().toString());
+  });
+
+  it("should create a batch of streams", async () => {
+    const { underlyingToken, amount, nowTS } = await setupUnderlying();
+
+    const legs = [
+      {
+        recipient: recipientKP.publicKey,
+        amount: TokenAmount.parse(underlyingToken, "3"),
+      },
+      {
+        recipient: crankerKP.publicKey,
+        amount: TokenAmount.parse(underlyingToken, "7"),
+      },
+    ];
+    const { tx, streams } = await adminSDK.venko.createStreamBatch({
+      underlyingToken,
+      legs,
+      startTS: nowTS,
+      endTS: nowTS + 3,
+    });
+    await expectTXTable(tx, "create stream batch", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    for (const [i, { stream }] of streams.entries()) {
+      const streamData = await adminSDK.venko.fetchStream(stream);
+      expect(streamData?.initialAmount.toString()).to.eq(
+        legs[i]?.amount.toU64().toString()
+      );
+    }
+
+    const [creatorIndex] = await findCreatorIndexAddress(
+      adminSDK.provider.wallet.publicKey
+    );
+    const indexedStreams = await adminSDK.venko.fetchIndexedStreams(
+      creatorIndex
+    );
+    for (const { stream } of streams) {
+      expect(indexedStreams.map((s) => s.toString())).to.include(
+        stream.toString()
+      );
+    }
+
+    for (const [i, { recipient }] of legs.entries()) {
+      const [recipientIndex] = await findRecipientIndexAddress(recipient);
+      const recipientStreams = await adminSDK.venko.fetchIndexedStreams(
+        recipientIndex
+      );
+      expect(recipientStreams.map((s) => s.toString())).to.include(
+        streams[i]?.stream.toString()
+      );
+    }
+  });
+
+  it("should release a vesting distributor to its claimants", async () => {
+    const { underlyingToken, amount, nowTS } = await setupUnderlying();
+
+    const recipientAllocation = TokenAmount.parse(underlyingToken, "3").toU64();
+    const crankerAllocation = TokenAmount.parse(underlyingToken, "7").toU64();
+    const recipientLeaf = vestingLeaf(
+      recipientKP.publicKey,
+      recipientAllocation
+    );
+    const crankerLeaf = vestingLeaf(crankerKP.publicKey, crankerAllocation);
+
+    const { distributor, tx } = await adminSDK.venko.newVestingDistributor({
+      root: hashPair(recipientLeaf, crankerLeaf),
+      maxTotalClaim: amount,
+      startTS: nowTS,
+      endTS: nowTS + 3,
+    });
+    await expectTXTable(tx, "create vesting distributor", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    // wait for distribution to be over...
+    await sleep(5_000);
+
+    const claimTX = await recipientSDK.venko.claimVested({
+      distributor,
+      allocation: recipientAllocation,
+      proof: [crankerLeaf],
+    });
+    await expectTXTable(claimTX, "claim vested", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    const recipientUnderlyingATA = await getATAAddress({
+      mint: underlyingToken.mintAccount,
+      owner: recipientKP.publicKey,
+    });
+    const recipientUnderlying = await provider.connection.getTokenAccountBalance(
+      recipientUnderlyingATA
+    );
+    expect(recipientUnderlying.value.amount).to.eq(
+      recipientAllocation.toString()
+    );
+  });
+
+  it("should set and update stream metadata", async () => {
+    const { amount, nowTS } = await setupUnderlying();
+    const externalRef = sha256(Buffer.from("invoice #1", "utf8"));
+
+    const {
+      tx,
+      stream,
+      token: streamToken,
+    } = await adminSDK.venko.createStream({
       amount,
       startTS: nowTS,
       endTS: nowTS + 3,
       recipient: recipientKP.publicKey,
+      metadata: { name: "Grant", memo: "Q1 grant", externalRef },
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    const created = await adminSDK.venko.fetchStream(stream);
+    expect(created?.creator).to.eqAddress(adminSDK.provider.wallet.publicKey);
+    expect(created?.version).to.eq(1);
+    const createdMetadata = created
+      ? decodeStreamMetadata(created.metadata)
+      : null;
+    expect(createdMetadata?.name).to.eq("Grant");
+    expect(createdMetadata?.memo).to.eq("Q1 grant");
+    expect(createdMetadata?.externalRef.equals(externalRef)).to.be.true;
+
+    await expectTX(
+      recipientSDK.venko.updateStreamMetadata({
+        streamMint: streamToken.mintAccount,
+        metadata: { name: "Stolen" },
+      }),
+      "recipient cannot update metadata"
+    ).to.be.rejected;
+
+    await expectTX(
+      adminSDK.venko.updateStreamMetadata({
+        streamMint: streamToken.mintAccount,
+        metadata: { name: "Grant (amended)", externalRef },
+      }),
+      "creator updates metadata"
+    ).to.be.fulfilled;
+
+    const updated = await adminSDK.venko.fetchStream(stream);
+    expect(
+      updated ? decodeStreamMetadata(updated.metadata).name : null
+    ).to.eq("Grant (amended)");
+  });
+
+  it("should keep the schedule of a revoked stream", async () => {
+    const { amount, nowTS } = await setupUnderlying();
+
+    const {
+      tx,
+      stream,
+      token: streamToken,
+    } = await adminSDK.venko.createStream({
+      amount,
+      startTS: nowTS,
+      endTS: nowTS + 1_000,
+      recipient: recipientKP.publicKey,
       revoker: adminSDK.provider.wallet.publicKey,
     });
     await expectTXTable(tx, "create stream", {
       verbosity: "error",
     }).to.be.fulfilled;
 
+    await expectTX(
+      adminSDK.venko.revoke({ streamMint: streamToken.mintAccount }),
+      "revoke stream"
+    ).to.be.fulfilled;
+
+    const revoked = await adminSDK.venko.fetchStream(stream);
+    expect(revoked?.status).to.deep.eq({ revoked: {} });
+    expect(revoked?.endTs.toNumber()).to.eq(nowTS + 1_000);
+    expect(revoked?.redeemedAmount.toNumber()).to.eq(0);
+    expect(revoked?.revokedAt.isZero()).to.be.false;
+
+    await expectTX(
+      adminSDK.venko.revoke({ streamMint: streamToken.mintAccount }),
+      "cannot revoke twice"
+    ).to.be.rejected;
+  });
+
+  it("should only allow the beneficiary to redeem", async () => {
+    const { amount, nowTS } = await setupUnderlying();
+
+    const {
+      tx,
+      stream,
+      token: streamToken,
+    } = await adminSDK.venko.createStream({
+      amount,
+      startTS: nowTS,
+      endTS: nowTS + 3,
+      recipient: recipientKP.publicKey,
+      transferable: false,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    const streamData = await adminSDK.venko.fetchStream(stream);
+    expect(streamData?.beneficiary).to.eqAddress(recipientKP.publicKey);
+
+    const recipientStreamATA = await getATAAddress({
+      mint: streamToken.mintAccount,
+      owner: recipientKP.publicKey,
+    });
+    const crankerStreamATA = await getOrCreateATA({
+      provider: recipientSDK.provider,
+      mint: streamToken.mintAccount,
+      owner: crankerKP.publicKey,
+    });
+    const transferAmount = TokenAmount.parse(streamToken, "1");
+    const transferTX = () =>
+      recipientSDK.provider.newTX([
+        SPLToken.createTransferInstruction(
+          TOKEN_PROGRAM_ID,
+          recipientStreamATA,
+          crankerStreamATA.address,
+          recipientKP.publicKey,
+          [],
+          transferAmount.toU64()
+        ),
+      ]);
+    await expectTX(
+      recipientSDK.provider.newTX([crankerStreamATA.instruction]),
+      "create transferee account"
+    ).to.be.fulfilled;
+    await expectTX(transferTX(), "stream tokens are frozen").to.be.rejected;
+
     // wait for stream to be over...
     await sleep(5_000);
 
+    await expectTX(
+      crankerSDK.venko.redeem({ amount: transferAmount }),
+      "transferee cannot redeem"
+    ).to.be.rejected;
+
     const claimTX = await recipientSDK.venko.redeem({
       amount: TokenAmount.parse(streamToken, "1"),
     });
     await expectTXTable(claimTX, "redeem stream", {
       verbosity: "error",
     }).to.be.fulfilled;
+
+    // the remaining tokens are frozen again
+    await expectTX(transferTX(), "still frozen").to.be.rejected;
+  });
+
+  it("should allow the creator to cancel an escrow stream", async () => {
+    const { amount, adminUnderlyingTokens, nowTS } = await setupUnderlying();
+
+    const { tx: unboundedTX } = await adminSDK.venko.createStream({
+      amount,
+      startTS: nowTS,
+      endTS: nowTS + 1_000,
+      cancelUntilTS: nowTS + 1_001,
+      recipient: recipientKP.publicKey,
+    });
+    await expectTX(
+      unboundedTX,
+      "cannot cancel after the end of the stream"
+    ).to.be.rejected;
+
+    const {
+      tx,
+      stream,
+      token: streamToken,
+    } = await adminSDK.venko.createStream({
+      amount,
+      startTS: nowTS,
+      endTS: nowTS + 1_000,
+      cancelUntilTS: nowTS + 1_000,
+      recipient: recipientKP.publicKey,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    await sleep(2_000);
+
+    await expectTX(
+      recipientSDK.venko.redeem({
+        amount: TokenAmount.parse(streamToken, "1"),
+      }),
+      "cannot redeem during cancellation window"
+    ).to.be.rejected;
+
+    await expectTX(
+      adminSDK.venko.cancelStream({ streamMint: streamToken.mintAccount }),
+      "cancel stream"
+    ).to.be.fulfilled;
+
+    const cancelled = await adminSDK.venko.fetchStream(stream);
+    expect(cancelled?.status).to.deep.eq({ cancelled: {} });
+
+    const adminBalance = await provider.connection.getTokenAccountBalance(
+      adminUnderlyingTokens
+    );
+    expect(adminBalance.value.amount).to.eq(amount.toU64().toString());
+  });
+
+  it("should allow the recipient to reject a stream", async () => {
+    const { amount, adminUnderlyingTokens, nowTS } = await setupUnderlying();
+
+    const {
+      tx,
+      stream,
+      token: streamToken,
+    } = await adminSDK.venko.createStream({
+      amount,
+      startTS: nowTS,
+      endTS: nowTS + 1_000,
+      recipient: recipientKP.publicKey,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    await expectTX(
+      recipientSDK.venko.rejectStream({ streamMint: streamToken.mintAccount }),
+      "reject stream"
+    ).to.be.fulfilled;
+
+    const rejected = await adminSDK.venko.fetchStream(stream);
+    expect(rejected?.status).to.deep.eq({ rejected: {} });
+
+    const adminBalance = await provider.connection.getTokenAccountBalance(
+      adminUnderlyingTokens
+    );
+    expect(adminBalance.value.amount).to.eq(amount.toU64().toString());
+
+    await expectTX(
+      adminSDK.venko.closeStream({ streamMint: streamToken.mintAccount }),
+      "close stream"
+    ).to.be.fulfilled;
+  });
+
+  it("should start a pending stream when it is accepted", async () => {
+    const { amount, nowTS } = await setupUnderlying();
+
+    const {
+      tx,
+      stream,
+      token: streamToken,
+    } = await adminSDK.venko.createStream({
+      amount,
+      startTS: 0,
+      cliffTS: 10,
+      endTS: 100,
+      cancelUntilTS: 50,
+      acceptanceDeadline: nowTS + 1_000,
+      recipient: recipientKP.publicKey,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    const pending = await adminSDK.venko.fetchStream(stream);
+    expect(pending?.status).to.deep.eq({ pending: {} });
+
+    await expectTX(
+      adminSDK.venko.cancelStream({ streamMint: streamToken.mintAccount }),
+      "cannot reclaim before the deadline"
+    ).to.be.rejected;
+
+    await expectTX(
+      recipientSDK.venko.acceptStream({ streamMint: streamToken.mintAccount }),
+      "accept stream"
+    ).to.be.fulfilled;
+
+    const accepted = await adminSDK.venko.fetchStream(stream);
+    expect(accepted?.status).to.deep.eq({ active: {} });
+    const startTS = accepted?.startTs.toNumber() ?? 0;
+    expect(startTS).to.be.gte(nowTS);
+    expect(accepted?.endTs.toNumber()).to.eq(startTS + 100);
+    expect(accepted?.cancelUntilTs.toNumber()).to.eq(startTS + 50);
+  });
+
+  it("should allow the recipient to reject a pending stream", async () => {
+    const { amount, adminUnderlyingTokens, nowTS } = await setupUnderlying();
+
+    const {
+      tx,
+      stream,
+      token: streamToken,
+    } = await adminSDK.venko.createStream({
+      amount,
+      startTS: 0,
+      endTS: 100,
+      acceptanceDeadline: nowTS + 1_000,
+      recipient: recipientKP.publicKey,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    await expectTX(
+      recipientSDK.venko.rejectStream({ streamMint: streamToken.mintAccount }),
+      "reject pending stream"
+    ).to.be.fulfilled;
+
+    const rejected = await adminSDK.venko.fetchStream(stream);
+    expect(rejected?.status).to.deep.eq({ rejected: {} });
+
+    const adminBalance = await provider.connection.getTokenAccountBalance(
+      adminUnderlyingTokens
+    );
+    expect(adminBalance.value.amount).to.eq(amount.toU64().toString());
+  });
+
+  it("should allow reclaiming an expired pending stream", async () => {
+    const { amount, adminUnderlyingTokens, nowTS } = await setupUnderlying();
+
+    const {
+      tx,
+      stream,
+      token: streamToken,
+    } = await adminSDK.venko.createStream({
+      amount,
+      startTS: 0,
+      endTS: 100,
+      acceptanceDeadline: nowTS + 5,
+      recipient: recipientKP.publicKey,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    await sleep(7_000);
+
+    await expectTX(
+      recipientSDK.venko.acceptStream({ streamMint: streamToken.mintAccount }),
+      "cannot accept after the deadline"
+    ).to.be.rejected;
+
+    await expectTX(
+      adminSDK.venko.cancelStream({ streamMint: streamToken.mintAccount }),
+      "reclaim expired stream"
+    ).to.be.fulfilled;
+
+    const cancelled = await adminSDK.venko.fetchStream(stream);
+    expect(cancelled?.status).to.deep.eq({ cancelled: {} });
+
+    const adminBalance = await provider.connection.getTokenAccountBalance(
+      adminUnderlyingTokens
+    );
+    expect(adminBalance.value.amount).to.eq(amount.toU64().toString());
+  });
+
+  it("should charge the author fee on redemptions", async () => {
+    const { underlyingToken, amount, nowTS } = await setupUnderlying();
+
+    const {
+      tx,
+      stream,
+      token: streamToken,
+    } = await adminSDK.venko.createStream({
+      amount,
+      startTS: nowTS,
+      endTS: nowTS + 3,
+      recipient: recipientKP.publicKey,
+      authorFeeTo: crankerKP.publicKey,
+      withdrawFeeBps: 100,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    const streamData = await adminSDK.venko.fetchStream(stream);
+    expect(streamData?.authorFeeTo).to.eqAddress(crankerKP.publicKey);
+
+    // wait for stream to be over...
+    await sleep(5_000);
+
+    const claimTX = await recipientSDK.venko.redeem({
+      amount: TokenAmount.parse(streamToken, "10"),
+    });
+    await expectTXTable(claimTX, "redeem stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    const authorFeeATA = await getATAAddress({
+      mint: underlyingToken.mintAccount,
+      owner: crankerKP.publicKey,
+    });
+    const authorFee = await provider.connection.getTokenAccountBalance(
+      authorFeeATA
+    );
+    expect(Number(authorFee.value.amount)).to.be.gt(0);
+
+    const recipientUnderlyingATA = await getATAAddress({
+      mint: underlyingToken.mintAccount,
+      owner: recipientKP.publicKey,
+    });
+    const recipientUnderlying = await provider.connection.getTokenAccountBalance(
+      recipientUnderlyingATA
+    );
+    expect(Number(recipientUnderlying.value.amount)).to.be.lt(
+      amount.toU64().toNumber()
+    );
+  });
+
+  it("should not charge fees on refunds", async () => {
+    const { amount, adminUnderlyingTokens, nowTS } = await setupUnderlying();
+
+    await expectTX(
+      (
+        await adminSDK.venko.createStream({
+          amount,
+          startTS: nowTS,
+          endTS: nowTS + 1_000,
+          recipient: recipientKP.publicKey,
+          authorFeeTo: crankerKP.publicKey,
+          withdrawFeeBps: 1_001,
+        })
+      ).tx,
+      "fee above the cap"
+    ).to.be.rejected;
+
+    const {
+      tx,
+      stream,
+      token: streamToken,
+    } = await adminSDK.venko.createStream({
+      amount,
+      startTS: nowTS,
+      endTS: nowTS + 1_000,
+      cancelUntilTS: nowTS + 1_000,
+      recipient: recipientKP.publicKey,
+      authorFeeTo: crankerKP.publicKey,
+      withdrawFeeBps: 1_000,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    await expectTX(
+      adminSDK.venko.cancelStream({ streamMint: streamToken.mintAccount }),
+      "cancel stream"
+    ).to.be.fulfilled;
+
+    const adminBalance = await provider.connection.getTokenAccountBalance(
+      adminUnderlyingTokens
+    );
+    expect(adminBalance.value.amount).to.eq(amount.toU64().toString());
+
+    // the fee is restored after the refund
+    const streamData = await adminSDK.venko.fetchStream(stream);
+    expect(streamData).to.not.be.null;
+    const crateData = await provider.connection.getAccountInfo(
+      streamData?.crateToken ?? stream
+    );
+    // offset of CrateToken::withdraw_fee_bps
+    expect(crateData?.data.readUInt16LE(203)).to.eq(1_000);
+
+    const { tx: revocableTX, token: revocableToken } =
+      await adminSDK.venko.createStream({
+        amount,
+        startTS: nowTS,
+        endTS: nowTS + 1_000,
+        revoker: adminKP.publicKey,
+        recipient: recipientKP.publicKey,
+        authorFeeTo: crankerKP.publicKey,
+        withdrawFeeBps: 1_000,
+      });
+    await expectTXTable(revocableTX, "create revocable stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    await expectTX(
+      adminSDK.venko.revoke({ streamMint: revocableToken.mintAccount }),
+      "revoke stream"
+    ).to.be.fulfilled;
+
+    const revokedBalance = await provider.connection.getTokenAccountBalance(
+      adminUnderlyingTokens
+    );
+    expect(revokedBalance.value.amount).to.eq(amount.toU64().toString());
+  });
+
+  it("should only allow the creator to close a drained stream", async () => {
+    const { amount, nowTS } = await setupUnderlying();
+
+    const {
+      tx,
+      stream,
+      token: streamToken,
+    } = await adminSDK.venko.createStream({
+      amount,
+      startTS: nowTS,
+      endTS: nowTS + 1,
+      recipient: recipientKP.publicKey,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    await expectTX(
+      adminSDK.venko.closeStream({ streamMint: streamToken.mintAccount }),
+      "cannot close before the stream is drained"
+    ).to.be.rejected;
+
+    await sleep(3_000);
+    await expectTX(
+      recipientSDK.venko.redeem({
+        amount: TokenAmount.parse(streamToken, "10"),
+      }),
+      "redeem stream"
+    ).to.be.fulfilled;
+
+    await expectTX(
+      recipientSDK.venko.closeStream({
+        streamMint: streamToken.mintAccount,
+      }),
+      "only the creator may close"
+    ).to.be.rejected;
+
+    await expectTX(
+      adminSDK.venko.closeStream({ streamMint: streamToken.mintAccount }),
+      "close stream"
+    ).to.be.fulfilled;
+    expect(await adminSDK.venko.fetchStream(stream)).to.be.null;
+  });
+  it("should transfer and renounce the revoker role", async () => {
+    const { amount, nowTS } = await setupUnderlying();
+
+    const {
+      tx,
+      stream,
+      token: streamToken,
+    } = await adminSDK.venko.createStream({
+      amount,
+      startTS: nowTS,
+      endTS: nowTS + 1_000,
+      recipient: recipientKP.publicKey,
+      revoker: adminSDK.provider.wallet.publicKey,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+    const streamMint = streamToken.mintAccount;
+
+    await expectTX(
+      recipientSDK.venko.transferRevoker({
+        streamMint,
+        newRevoker: recipientKP.publicKey,
+      }),
+      "only the revoker may nominate"
+    ).to.be.rejected;
+
+    await expectTX(
+      adminSDK.venko.transferRevoker({
+        streamMint,
+        newRevoker: crankerKP.publicKey,
+      }),
+      "nominate revoker"
+    ).to.be.fulfilled;
+    const nominated = await adminSDK.venko.fetchStream(stream);
+    expect(nominated?.pendingRevoker).to.eqAddress(crankerKP.publicKey);
+
+    await expectTX(
+      recipientSDK.venko.acceptRevoker({ streamMint }),
+      "only the nominee may accept"
+    ).to.be.rejected;
+
+    await expectTX(
+      crankerSDK.venko.acceptRevoker({ streamMint }),
+      "accept revoker"
+    ).to.be.fulfilled;
+    const accepted = await adminSDK.venko.fetchStream(stream);
+    expect(accepted?.revoker).to.eqAddress(crankerKP.publicKey);
+    expect(accepted?.pendingRevoker).to.eqAddress(PublicKey.default);
+
+    await expectTX(
+      adminSDK.venko.renounceRevoker({ streamMint }),
+      "the previous revoker may not renounce"
+    ).to.be.rejected;
+
+    await expectTX(
+      crankerSDK.venko.scheduleRevocation({
+        streamMint,
+        revokeTS: nowTS + 500,
+      }),
+      "schedule revocation"
+    ).to.be.fulfilled;
+
+    await expectTX(
+      crankerSDK.venko.renounceRevoker({ streamMint }),
+      "renounce revoker"
+    ).to.be.fulfilled;
+    const renounced = await adminSDK.venko.fetchStream(stream);
+    expect(renounced?.revoker).to.eqAddress(PublicKey.default);
+    expect(renounced?.scheduledRevokeTs.toNumber()).to.eq(0);
+
+    await expectTX(
+      crankerSDK.venko.revoke({ streamMint }),
+      "cannot revoke after renouncing"
+    ).to.be.rejected;
+  });
+  it("should redeem all redeemable tokens via redeem max", async () => {
+    const { underlyingToken, amount, nowTS } = await setupUnderlying();
+
+    const { tx, token: streamToken } = await adminSDK.venko.createStream({
+      amount,
+      startTS: nowTS,
+      endTS: nowTS + 8,
+      recipient: recipientKP.publicKey,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    const recipientStreamATA = await getATAAddress({
+      mint: streamToken.mintAccount,
+      owner: recipientKP.publicKey,
+    });
+    const recipientUnderlyingATA = await getATAAddress({
+      mint: underlyingToken.mintAccount,
+      owner: recipientKP.publicKey,
+    });
+    const getBalance = async (account: PublicKey): Promise<string> =>
+      (await provider.connection.getTokenAccountBalance(account)).value.amount;
+
+    /**
+     * Redeems the max and returns the amount of the emitted RedeemEvent.
+     */
+    const redeemMax = async (): Promise<string> => {
+      const redeemTX = await recipientSDK.venko.redeemMax({
+        streamMint: streamToken.mintAccount,
+      });
+      const receipt = await (await redeemTX.send()).wait();
+      const events = VENKO_CODERS.Venko.parseProgramLogEvents(
+        receipt.response.meta?.logMessages ?? []
+      );
+      const redeemEvent = events.find((e) => e.name === "RedeemEvent");
+      expect(redeemEvent).to.exist;
+      return (redeemEvent?.data as { amount: BN }).amount.toString();
+    };
+
+    // only part of the stream has been released
+    await sleep(2_000);
+    const partial = await redeemMax();
+    expect(partial).to.not.eq("0");
+    expect(partial).to.not.eq(amount.toU64().toString());
+    expect(await getBalance(recipientUnderlyingATA)).to.eq(partial);
+    const remaining = await getBalance(recipientStreamATA);
+    expect(amount.toU64().sub(new BN(partial)).toString()).to.eq(remaining);
+
+    // once the stream is over, the entire balance is redeemed
+    await sleep(8_000);
+    expect(await redeemMax()).to.eq(remaining);
+    expect(await getBalance(recipientStreamATA)).to.eq("0");
+    expect(await getBalance(recipientUnderlyingATA)).to.eq(
+      amount.toU64().toString()
+    );
+  });
+  it("should top up a stream", async () => {
+    const { underlyingToken, amount, nowTS } = await setupUnderlying();
+
+    const {
+      tx,
+      stream,
+      token: streamToken,
+    } = await adminSDK.venko.createStream({
+      amount: TokenAmount.parse(underlyingToken, "6"),
+      startTS: nowTS,
+      endTS: nowTS + 1_000,
+      recipient: recipientKP.publicKey,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    const outsiderAmount = TokenAmount.parse(underlyingToken, "1");
+    const recipientUnderlying = await getOrCreateATA({
+      provider: adminSDK.provider,
+      owner: recipientKP.publicKey,
+      mint: underlyingToken.mintAccount,
+    });
+    await expectTX(
+      adminSDK.provider.newTX([
+        recipientUnderlying.instruction,
+        SPLToken.createMintToInstruction(
+          TOKEN_PROGRAM_ID,
+          underlyingToken.mintAccount,
+          recipientUnderlying.address,
+          adminSDK.provider.wallet.publicKey,
+          [],
+          outsiderAmount.toU64()
+        ),
+      ]),
+      "fund recipient"
+    ).to.be.fulfilled;
+    await expectTX(
+      recipientSDK.venko.topUp({
+        streamMint: streamToken.mintAccount,
+        amount: outsiderAmount,
+      }),
+      "only the creator may top up"
+    ).to.be.rejected;
+
+    await expectTX(
+      adminSDK.venko.topUp({
+        streamMint: streamToken.mintAccount,
+        amount: TokenAmount.parse(underlyingToken, "0"),
+        recipient: recipientKP.publicKey,
+      }),
+      "cannot top up nothing"
+    ).to.be.rejected;
+
+    await expectTX(
+      adminSDK.venko.topUp({
+        streamMint: streamToken.mintAccount,
+        amount: TokenAmount.parse(underlyingToken, "4"),
+        recipient: recipientKP.publicKey,
+      }),
+      "top up stream"
+    ).to.be.fulfilled;
+
+    const streamData = await adminSDK.venko.fetchStream(stream);
+    expect(streamData?.initialAmount.toString()).to.eq(
+      amount.toU64().toString()
+    );
+
+    const recipientStreamATA = await getATAAddress({
+      mint: streamToken.mintAccount,
+      owner: recipientKP.publicKey,
+    });
+    const recipientStream = await provider.connection.getTokenAccountBalance(
+      recipientStreamATA
+    );
+    expect(recipientStream.value.amount).to.eq(amount.toU64().toString());
+  });
+  it("should keep vested tokens when revoking unvested", async () => {
+    const { underlyingToken, amount, adminUnderlyingTokens, nowTS } =
+      await setupUnderlying();
+
+    const {
+      tx,
+      stream,
+      token: streamToken,
+    } = await adminSDK.venko.createStream({
+      amount,
+      startTS: nowTS,
+      endTS: nowTS + 8,
+      recipient: recipientKP.publicKey,
+      revoker: adminSDK.provider.wallet.publicKey,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+
+    await sleep(2_000);
+    await expectTX(
+      adminSDK.venko.revoke({
+        streamMint: streamToken.mintAccount,
+        unvestedOnly: true,
+      }),
+      "revoke unvested"
+    ).to.be.fulfilled;
+
+    const revoked = await adminSDK.venko.fetchStream(stream);
+    expect(revoked?.status).to.deep.eq({ revoked: {} });
+
+    const adminBalance = await provider.connection.getTokenAccountBalance(
+      adminUnderlyingTokens
+    );
+    const unvested = new BN(adminBalance.value.amount);
+    expect(unvested.isZero()).to.be.false;
+    expect(unvested.lt(amount.toU64())).to.be.true;
+
+    // vesting stops at the revocation
+    await sleep(8_000);
+    await expectTX(
+      recipientSDK.venko.redeemMax({ streamMint: streamToken.mintAccount }),
+      "redeem vested tokens"
+    ).to.be.fulfilled;
+
+    const recipientUnderlyingATA = await getATAAddress({
+      mint: underlyingToken.mintAccount,
+      owner: recipientKP.publicKey,
+    });
+    const recipientUnderlying = await provider.connection.getTokenAccountBalance(
+      recipientUnderlyingATA
+    );
+    expect(recipientUnderlying.value.amount).to.eq(
+      amount.toU64().sub(unvested).toString()
+    );
+  });
+  it("should schedule, cancel and execute revocations", async () => {
+    const { amount, adminUnderlyingTokens, nowTS } = await setupUnderlying();
+
+    const {
+      tx,
+      stream,
+      token: streamToken,
+    } = await adminSDK.venko.createStream({
+      amount,
+      startTS: nowTS,
+      endTS: nowTS + 1_000,
+      recipient: recipientKP.publicKey,
+      revoker: adminSDK.provider.wallet.publicKey,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+    const streamMint = streamToken.mintAccount;
+
+    await expectTX(
+      adminSDK.venko.scheduleRevocation({
+        streamMint,
+        revokeTS: nowTS + 500,
+      }),
+      "schedule revocation"
+    ).to.be.fulfilled;
+    const scheduled = await adminSDK.venko.fetchStream(stream);
+    expect(scheduled?.scheduledRevokeTs.toNumber()).to.eq(nowTS + 500);
+
+    await expectTX(
+      crankerSDK.venko.executeRevocation({ streamMint }),
+      "cannot execute before the revocation time"
+    ).to.be.rejected;
+
+    await expectTX(
+      adminSDK.venko.cancelRevocation({ streamMint }),
+      "cancel revocation"
+    ).to.be.fulfilled;
+    const cancelled = await adminSDK.venko.fetchStream(stream);
+    expect(cancelled?.scheduledRevokeTs.toNumber()).to.eq(0);
+
+    await expectTX(
+      crankerSDK.venko.executeRevocation({ streamMint }),
+      "cannot execute a cancelled revocation"
+    ).to.be.rejected;
+
+    const revokeTS = Math.floor(new Date().getTime() / 1_000) + 3;
+    await expectTX(
+      adminSDK.venko.scheduleRevocation({ streamMint, revokeTS }),
+      "reschedule revocation"
+    ).to.be.fulfilled;
+
+    await sleep(5_000);
+    await expectTX(
+      adminSDK.venko.cancelRevocation({ streamMint }),
+      "cannot cancel a passed revocation"
+    ).to.be.rejected;
+
+    // anyone may execute a revocation which has passed
+    await expectTX(
+      crankerSDK.venko.executeRevocation({ streamMint }),
+      "execute revocation"
+    ).to.be.fulfilled;
+
+    const revoked = await adminSDK.venko.fetchStream(stream);
+    expect(revoked?.status).to.deep.eq({ revoked: {} });
+    expect(revoked?.revokedAt.toNumber()).to.eq(revokeTS);
+
+    const adminBalance = await provider.connection.getTokenAccountBalance(
+      adminUnderlyingTokens
+    );
+    const unvested = new BN(adminBalance.value.amount);
+    expect(unvested.isZero()).to.be.false;
+    expect(unvested.lt(amount.toU64())).to.be.true;
+  });
+  it("should pause and resume a stream", async () => {
+    const { amount, nowTS } = await setupUnderlying();
+
+    const {
+      tx,
+      stream,
+      token: streamToken,
+    } = await adminSDK.venko.createStream({
+      amount,
+      startTS: nowTS,
+      endTS: nowTS + 1_000,
+      recipient: recipientKP.publicKey,
+      revoker: adminSDK.provider.wallet.publicKey,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+    const streamMint = streamToken.mintAccount;
+
+    await expectTX(
+      recipientSDK.venko.pause({ streamMint }),
+      "only the revoker may pause"
+    ).to.be.rejected;
+
+    await expectTX(
+      adminSDK.venko.pause({ streamMint }),
+      "pause stream"
+    ).to.be.fulfilled;
+    const paused = await adminSDK.venko.fetchStream(stream);
+    expect(paused?.pausedAt.isZero()).to.be.false;
+
+    await expectTX(
+      adminSDK.venko.pause({ streamMint }),
+      "cannot pause twice"
+    ).to.be.rejected;
+
+    await sleep(3_000);
+    await expectTX(
+      adminSDK.venko.resume({ streamMint }),
+      "resume stream"
+    ).to.be.fulfilled;
+    const resumed = await adminSDK.venko.fetchStream(stream);
+    expect(resumed?.pausedAt.isZero()).to.be.true;
+    expect(resumed?.pausedDuration.toNumber()).to.be.gt(0);
+
+    await expectTX(
+      adminSDK.venko.resume({ streamMint }),
+      "cannot resume an active stream"
+    ).to.be.rejected;
+  });
+  it("should thaw the tokens of a cancelled bound stream", async () => {
+    const { amount, nowTS } = await setupUnderlying();
+
+    const {
+      tx,
+      stream,
+      token: streamToken,
+    } = await adminSDK.venko.createStream({
+      amount,
+      startTS: nowTS,
+      endTS: nowTS + 1_000,
+      cancelUntilTS: nowTS + 1_000,
+      recipient: recipientKP.publicKey,
+      transferable: false,
+    });
+    await expectTXTable(tx, "create stream", {
+      verbosity: "error",
+    }).to.be.fulfilled;
+    const streamMint = streamToken.mintAccount;
+
+    await expectTX(
+      recipientSDK.venko.thawStreamTokens({ streamMint }),
+      "cannot thaw an active stream"
+    ).to.be.rejected;
+
+    await expectTX(
+      adminSDK.venko.cancelStream({ streamMint }),
+      "cancel stream"
+    ).to.be.fulfilled;
+
+    await expectTX(
+      recipientSDK.venko.thawStreamTokens({ streamMint }),
+      "thaw stream tokens"
+    ).to.be.fulfilled;
+
+    const recipientStreamATA = await getATAAddress({
+      mint: streamMint,
+      owner: recipientKP.publicKey,
+    });
+    await expectTX(
+      recipientSDK.provider.newTX([
+        SPLToken.createBurnInstruction(
+          TOKEN_PROGRAM_ID,
+          streamMint,
+          recipientStreamATA,
+          recipientKP.publicKey,
+          [],
+          amount.toU64()
+        ),
+      ]),
+      "burn stream tokens"
+    ).to.be.fulfilled;
+
+    await expectTX(
+      adminSDK.venko.closeStream({ streamMint }),
+      "close stream"
+    ).to.be.fulfilled;
+    expect(await adminSDK.venko.fetchStream(stream)).to.be.null;
   });
 });